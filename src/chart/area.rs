@@ -0,0 +1,80 @@
+use std::collections::BTreeMap;
+use matrix::Matrix;
+use matrix::PointTrait;
+use ui::CoordinatePrecision;
+use super::cell::Cell;
+
+/// Default glyph for the area below the points
+pub const AREA_FILL: &str = "\u{2591}";
+
+/// Part of an area chart a cell belongs to
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum AreaPart {
+    /// The topmost point of a column
+    Point,
+    /// A cell between the point and the chart's bottom
+    Fill,
+}
+
+pub type AreaCell<T> = Cell<T, AreaPart>;
+
+/// Fill every column from its topmost point down to the row `bottom`
+pub fn fill<T: PointTrait>(matrix: &Matrix<T>, bottom: CoordinatePrecision) -> Matrix<AreaCell<T>> {
+    let mut columns: BTreeMap<CoordinatePrecision, T> = BTreeMap::new();
+    for row in matrix.rows.values() {
+        for (x, point) in row {
+            // The rows are visited from the bottom, so the topmost point wins
+            columns.insert(*x, point.clone());
+        }
+    }
+
+    let mut cells: Vec<AreaCell<T>> = vec![];
+    for point in columns.values() {
+        for y in bottom..point.y() {
+            cells.push(Cell::new(point.with_y(y), AreaPart::Fill));
+        }
+        cells.push(Cell::new(point.clone(), AreaPart::Point));
+    }
+
+    Matrix::from_vec(cells)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use point::Point;
+
+    fn parts(matrix: &Matrix<AreaCell<Point>>, x: CoordinatePrecision) -> Vec<Option<AreaPart>> {
+        (0..5).map(|y| matrix.get(y, x).map(|cell| cell.kind)).collect()
+    }
+
+    #[test]
+    fn fill_test() {
+        let matrix = Matrix::from_vec(vec![
+            Point::new(0, 3),
+            Point::new(1, 1),
+            Point::new(1, 2),
+            Point::new(3, 0),
+        ]);
+        let cells = fill(&matrix, 0);
+
+        assert_eq!(
+            vec![Some(AreaPart::Fill), Some(AreaPart::Fill), Some(AreaPart::Fill), Some(AreaPart::Point), None],
+            parts(&cells, 0)
+        );
+        assert_eq!(
+            vec![Some(AreaPart::Fill), Some(AreaPart::Fill), Some(AreaPart::Point), None, None],
+            parts(&cells, 1)
+        );
+        assert_eq!(vec![None, None, None, None, None], parts(&cells, 2));
+        assert_eq!(vec![Some(AreaPart::Point), None, None, None, None], parts(&cells, 3));
+    }
+
+    #[test]
+    fn fill_above_bottom_test() {
+        let matrix = Matrix::from_vec(vec![Point::new(0, 3)]);
+        let cells = fill(&matrix, 2);
+
+        assert_eq!(vec![None, None, Some(AreaPart::Fill), Some(AreaPart::Point), None], parts(&cells, 0));
+    }
+}
@@ -0,0 +1,164 @@
+use std::collections::BTreeMap;
+use matrix::Matrix;
+use matrix::PointTrait;
+use ui::CoordinatePrecision;
+
+/// Glyph for the cells between a candle's body and its high or low
+pub const CANDLE_WICK: &str = "\u{2502}";
+/// Glyph for the cells between a candle's open and close
+pub const CANDLE_BODY: &str = super::BLOCK_FULL;
+
+/// Part of a candlestick a point stands for
+///
+/// A candle is passed to the chart as four points (`High`, `Low`, `Open` and `Close`), so the scaling
+/// transforms apply to all of its prices. Before drawing, each column is filled with `Wick` and `Body` cells.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum CandlePart {
+    High,
+    Low,
+    Open,
+    Close,
+    Wick,
+    Body,
+}
+
+impl CandlePart {
+    /// Return the glyph used to draw the part
+    pub fn glyph(self) -> &'static str {
+        match self {
+            CandlePart::Open | CandlePart::Close | CandlePart::Body => CANDLE_BODY,
+            CandlePart::High | CandlePart::Low | CandlePart::Wick => CANDLE_WICK,
+        }
+    }
+
+    fn is_body(self) -> bool {
+        matches!(self, CandlePart::Open | CandlePart::Close | CandlePart::Body)
+    }
+}
+
+pub trait CandlePointTrait: PointTrait {
+    fn part(&self) -> CandlePart;
+    fn with_part(&self, part: CandlePart) -> Self;
+}
+
+/// Fill every column from its lowest to its highest point with wick and body cells
+///
+/// The body spans the rows of the `Open` and `Close` points in the column. If several candles collapsed
+/// into one column, they are drawn as a single candle.
+pub fn fill<T: CandlePointTrait>(matrix: &Matrix<T>) -> Matrix<T> {
+    let mut columns: BTreeMap<CoordinatePrecision, Vec<T>> = BTreeMap::new();
+    for row in matrix.rows.values() {
+        for (x, point) in row {
+            columns.entry(*x).or_default().push(point.clone());
+        }
+    }
+
+    let mut cells: Vec<T> = vec![];
+    for points in columns.values() {
+        let y_min = points.iter().map(|p| p.y()).min().unwrap();
+        let y_max = points.iter().map(|p| p.y()).max().unwrap();
+        let body: Vec<&T> = points.iter().filter(|p| p.part().is_body()).collect();
+        let body_range = match (body.iter().map(|p| p.y()).min(), body.iter().map(|p| p.y()).max()) {
+            (Some(body_min), Some(body_max)) => Some((body_min, body_max)),
+            _ => None,
+        };
+
+        // Prefer the close to represent the column, as it carries the most recent price
+        let template = points.iter().find(|p| p.part() == CandlePart::Close)
+            .or_else(|| body.first().cloned())
+            .unwrap_or(&points[0]);
+        for y in y_min..(y_max + 1) {
+            let part = match body_range {
+                Some((body_min, body_max)) if y >= body_min && y <= body_max => CandlePart::Body,
+                _ => CandlePart::Wick,
+            };
+            cells.push(template.with_part(part).with_y(y));
+        }
+    }
+
+    Matrix::from_vec(cells)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone)]
+    struct CandlePoint {
+        x: CoordinatePrecision,
+        y: CoordinatePrecision,
+        part: CandlePart,
+    }
+
+    impl PointTrait for CandlePoint {
+        fn x(&self) -> CoordinatePrecision { self.x }
+        fn y(&self) -> CoordinatePrecision { self.y }
+        fn with_x(&self, x: CoordinatePrecision) -> Self { CandlePoint { x, ..self.clone() } }
+        fn with_y(&self, y: CoordinatePrecision) -> Self { CandlePoint { y, ..self.clone() } }
+        fn with_x_y(&self, x: CoordinatePrecision, y: CoordinatePrecision) -> Self { CandlePoint { x, y, ..self.clone() } }
+    }
+
+    impl CandlePointTrait for CandlePoint {
+        fn part(&self) -> CandlePart { self.part }
+        fn with_part(&self, part: CandlePart) -> Self { CandlePoint { part, ..self.clone() } }
+    }
+
+    fn candle(x: CoordinatePrecision, open: CoordinatePrecision, high: CoordinatePrecision, low: CoordinatePrecision, close: CoordinatePrecision) -> Vec<CandlePoint> {
+        vec![
+            CandlePoint { x, y: high, part: CandlePart::High },
+            CandlePoint { x, y: low, part: CandlePart::Low },
+            CandlePoint { x, y: open, part: CandlePart::Open },
+            CandlePoint { x, y: close, part: CandlePart::Close },
+        ]
+    }
+
+    fn parts(matrix: &Matrix<CandlePoint>, x: CoordinatePrecision) -> Vec<Option<CandlePart>> {
+        (0..8).map(|y| matrix.get(y, x).map(|p| p.part)).collect()
+    }
+
+    #[test]
+    fn fill_test() {
+        let mut points = candle(0, 2, 6, 1, 4);
+        points.append(&mut candle(1, 5, 5, 5, 5));
+        let cells = fill(&Matrix::from_vec(points));
+
+        assert_eq!(
+            vec![
+                None,
+                Some(CandlePart::Wick),
+                Some(CandlePart::Body),
+                Some(CandlePart::Body),
+                Some(CandlePart::Body),
+                Some(CandlePart::Wick),
+                Some(CandlePart::Wick),
+                None,
+            ],
+            parts(&cells, 0)
+        );
+        assert_eq!(
+            vec![None, None, None, None, None, Some(CandlePart::Body), None, None],
+            parts(&cells, 1)
+        );
+    }
+
+    #[test]
+    fn fill_merged_column_test() {
+        let mut points = candle(0, 2, 3, 2, 3);
+        points.append(&mut candle(0, 3, 6, 3, 5));
+        let cells = fill(&Matrix::from_vec(points));
+
+        assert_eq!(
+            vec![
+                None,
+                None,
+                Some(CandlePart::Body),
+                Some(CandlePart::Body),
+                Some(CandlePart::Body),
+                Some(CandlePart::Body),
+                Some(CandlePart::Wick),
+                None,
+            ],
+            parts(&cells, 0)
+        );
+    }
+}
@@ -1,17 +1,59 @@
+use std::collections::BTreeMap;
 use matrix::*;
 use super::configuration::*;
+use super::candlestick;
+use super::candlestick::CandlePointTrait;
+use super::area;
+use super::area::AreaCell;
+use super::cell;
+use super::line;
+use super::line::LineCell;
+use super::line::LineSegment;
 use super::padding::Padding;
+use super::series;
 use ui::CoordinatePrecision;
 
 pub struct Canvas {
     width: CoordinatePrecision,
     height: CoordinatePrecision,
     padding: Padding,
+    x_scala_height: CoordinatePrecision,
+    x_start: Option<CoordinatePrecision>,
+    y_start: Option<CoordinatePrecision>,
+    reference_rows: BTreeMap<CoordinatePrecision, ReferenceLine>,
 }
 
 impl Canvas {
     pub fn new(width: CoordinatePrecision, height: CoordinatePrecision, padding: Padding) -> Self {
-        Canvas { width, height, padding }
+        Canvas { width, height, padding, x_scala_height: 0, x_start: None, y_start: None, reference_rows: BTreeMap::new() }
+    }
+
+    /// Return a copy of the canvas which draws an x-axis scala of the given height below the points
+    pub fn with_x_scala_height(mut self, x_scala_height: CoordinatePrecision) -> Self {
+        self.x_scala_height = x_scala_height;
+
+        self
+    }
+
+    /// Return a copy of the canvas whose leftmost column is fixed instead of following the leftmost point
+    pub fn with_x_start(mut self, x_start: Option<CoordinatePrecision>) -> Self {
+        self.x_start = x_start;
+
+        self
+    }
+
+    /// Return a copy of the canvas whose lowest row is fixed instead of following the lowest point
+    pub fn with_y_start(mut self, y_start: Option<CoordinatePrecision>) -> Self {
+        self.y_start = y_start;
+
+        self
+    }
+
+    /// Return a copy of the canvas which draws the reference lines on the given rows
+    pub fn with_reference_rows(mut self, reference_rows: BTreeMap<CoordinatePrecision, ReferenceLine>) -> Self {
+        self.reference_rows = reference_rows;
+
+        self
     }
 
     pub fn draw_points<T: PointTrait>(&self, matrix: Matrix<T>) -> String {
@@ -42,24 +84,78 @@ impl Canvas {
     }
 
     pub fn draw_points_with_configuration<T: PointTrait>(&self, matrix: Matrix<T>, conf: &Configuration<T>) -> String {
-        if matrix.is_empty() {
+        self.draw_cells_with_configuration(&matrix, &matrix, conf)
+    }
+
+    /// Draw the candles given as open/high/low/close points
+    ///
+    /// The row labels are built from the original points, so only rows holding one of the prices are labeled
+    pub fn draw_candles_with_configuration<T: CandlePointTrait>(&self, matrix: Matrix<T>, conf: &dyn Configuration<T>) -> String {
+        self.draw_cells_with_configuration(&matrix, &candlestick::fill(&matrix), conf)
+    }
+
+    /// Draw the points connected by a line
+    pub fn draw_line_with_configuration<T: PointTrait>(&self, matrix: Matrix<T>, conf: &dyn Configuration<LineCell<T>>) -> String {
+        self.draw_cells_with_configuration(&cell::wrap(&matrix, LineSegment::Horizontal), &line::connect(&matrix), conf)
+    }
+
+    /// Draw the points of each series connected by a line
+    ///
+    /// Where the lines overlap, the series given first is drawn (see `series::overlay()`)
+    pub fn draw_lines_with_configuration<T: PointTrait>(&self, matrices: Vec<Matrix<T>>, conf: &dyn Configuration<LineCell<T>>) -> String {
+        let lines: Vec<Matrix<LineCell<T>>> = matrices.iter().map(line::connect).collect();
+
+        self.draw_cells_with_configuration(&cell::wrap(&series::overlay(&matrices), LineSegment::Horizontal), &series::overlay(&lines), conf)
+    }
+
+    /// Draw the points and fill the area below them down to the bottom of the canvas
+    pub fn draw_area_with_configuration<T: PointTrait>(&self, matrix: Matrix<T>, conf: &dyn Configuration<AreaCell<T>>) -> String {
+        let cells = match self.get_y_start(&matrix) {
+            Some(bottom) => area::fill(&matrix, bottom),
+            None => return "".to_string(),
+        };
+
+        self.draw_cells_with_configuration(&cell::wrap(&matrix, area::AreaPart::Point), &cells, conf)
+    }
+
+    fn draw_cells_with_configuration<T: PointTrait>(&self, matrix: &Matrix<T>, cells: &Matrix<T>, conf: &dyn Configuration<T>) -> String {
+        // Without points the window is only known if both starts are fixed
+        if matrix.is_empty() && (self.x_start.is_none() || self.y_start.is_none()) {
             return "".to_string();
         }
         let mut buffer = String::with_capacity((self.width * self.height) as usize);
-        let y_min = matrix.y_min().unwrap();
-
-        let y_start = if y_min < self.padding.bottom { 0 } else { y_min - self.padding.bottom };
+        let y_start = self.get_y_start(matrix).unwrap();
         let y_end = y_start + self.height + self.padding.top;
 
         for row_number in (y_start..y_end).rev() {
             let row = matrix.get_row(row_number);
             buffer.push_str(&conf.draw_row(row, row_number));
-            buffer.push_str(&self.draw_row_with_configuration(row_number, &matrix, conf));
+            buffer.push_str(&self.draw_row_with_configuration(row_number, cells, conf));
             buffer.push('\n');
         }
+        if self.x_scala_height > 0 {
+            buffer.push_str(&conf.draw_x_scala(&self.get_columns(matrix), self.x_scala_height));
+        }
         buffer
     }
 
+    /// Return the point of each column of the canvas (the lowest one, if a column holds several)
+    fn get_columns<T: PointTrait>(&self, matrix: &Matrix<T>) -> Vec<Option<T>> {
+        let x_start = self.get_x_start(matrix);
+        let x_end = x_start + self.width + self.padding.right;
+        let mut columns: Vec<Option<T>> = vec![None; (x_end - x_start) as usize];
+        for row in matrix.rows.values() {
+            for (x, point) in row.range(x_start..x_end) {
+                let column = &mut columns[(x - x_start) as usize];
+                if column.is_none() {
+                    *column = Some(point.clone());
+                }
+            }
+        }
+
+        columns
+    }
+
     pub fn draw_points_with_callback<F, T: PointTrait>(&self, matrix: Matrix<T>, draw_callback: F) -> String
         where F: Fn(Option<T>) -> String {
         let conf = super::configuration::CallbackConfiguration::new(
@@ -69,14 +165,37 @@ impl Canvas {
         self.draw_points_with_configuration(matrix, &conf)
     }
 
+    /// Return the lowest row of the canvas
+    fn get_y_start<T: PointTrait>(&self, matrix: &Matrix<T>) -> Option<CoordinatePrecision> {
+        if let Some(y_start) = self.y_start {
+            return Some(y_start);
+        }
+        let y_min = matrix.y_min()?;
+
+        Some(if y_min < self.padding.bottom { 0 } else { y_min - self.padding.bottom })
+    }
+
+    /// Return the leftmost column of the canvas
+    fn get_x_start<T: PointTrait>(&self, matrix: &Matrix<T>) -> CoordinatePrecision {
+        if let Some(x_start) = self.x_start {
+            return x_start;
+        }
+        let x_min = matrix.x_min().unwrap();
+
+        if x_min < self.padding.left { 0 } else { x_min - self.padding.bottom }
+    }
+
     fn draw_row_with_configuration<T: PointTrait>(&self, row_number: CoordinatePrecision, matrix: &Matrix<T>, conf: &Configuration<T>) -> String {
         let mut buffer = String::with_capacity(self.width as usize);
-        let x_min = matrix.x_min().unwrap();
-        let x_start = if x_min < self.padding.left { 0 } else { x_min - self.padding.bottom };
+        let x_start = self.get_x_start(matrix);
         let x_end = x_start + self.width + self.padding.right;
+        let reference_line = self.reference_rows.get(&row_number);
 
         for column in x_start..x_end {
-            buffer.push_str(&conf.draw_point(matrix.get(row_number, column)));
+            match (matrix.get(row_number, column), reference_line) {
+                (None, Some(line)) => buffer.push_str(&line.draw_cell((column - x_start) as usize, (x_end - x_start) as usize)),
+                (point, _) => buffer.push_str(&conf.draw_point(point)),
+            }
         }
 
         buffer
@@ -88,6 +207,38 @@ mod tests {
     use super::*;
     use point::Point;
 
+    #[test]
+    fn draw_x_scala_test() {
+        struct ScalaConfiguration {}
+        impl Configuration<Point> for ScalaConfiguration {
+            fn draw_row(&self, _: Option<&Row<Point>>, _: CoordinatePrecision) -> String { "".to_string() }
+            fn draw_point(&self, point: Option<Point>) -> String {
+                match point {
+                    Some(_) => "x".to_string(),
+                    None => " ".to_string(),
+                }
+            }
+            fn draw_x_scala(&self, columns: &[Option<Point>], height: CoordinatePrecision) -> String {
+                let line: String = columns.iter()
+                    .map(|column| match column {
+                        Some(point) => point.y.to_string(),
+                        None => "-".to_string(),
+                    })
+                    .collect();
+
+                format!("{}\n", line).repeat(height as usize)
+            }
+        }
+
+        let canvas = Canvas::new(4, 2, Padding::empty()).with_x_scala_height(2);
+        let output = canvas.draw_points_with_configuration(
+            Matrix::from_vec(vec![Point::new(0, 0), Point::new(2, 1), Point::new(2, 0)]),
+            &ScalaConfiguration {},
+        );
+
+        assert_eq!("  x \nx x \n0-0-\n0-0-\n", output);
+    }
+
     #[test]
     fn draw_points_with_symbol_test() {
         let canvas = Canvas::new(10, 2, Padding::empty());
@@ -0,0 +1,76 @@
+use matrix::Matrix;
+use matrix::PointTrait;
+use ui::CoordinatePrecision;
+
+/// Cell of a chart holding the point it was built from and the kind of glyph to draw
+///
+/// Styles which draw more cells than there are points (e.g. lines or areas) pass these to the configuration.
+/// The cell sits on the coordinates of its point, unless it was moved
+#[derive(Debug, Clone)]
+pub struct Cell<T: PointTrait, K: Copy> {
+    pub point: T,
+    pub kind: K,
+    x: Option<CoordinatePrecision>,
+    y: Option<CoordinatePrecision>,
+}
+
+impl<T: PointTrait, K: Copy> Cell<T, K> {
+    pub fn new(point: T, kind: K) -> Self {
+        Cell { point, kind, x: None, y: None }
+    }
+}
+
+impl<T: PointTrait, K: Copy> PointTrait for Cell<T, K> {
+    fn x(&self) -> CoordinatePrecision {
+        self.x.unwrap_or_else(|| self.point.x())
+    }
+
+    fn y(&self) -> CoordinatePrecision {
+        self.y.unwrap_or_else(|| self.point.y())
+    }
+
+    fn value(&self) -> f64 {
+        self.point.value()
+    }
+
+    fn with_value(&self, value: f64) -> Self {
+        let mut clone = self.clone();
+        clone.point = self.point.with_value(value);
+
+        clone
+    }
+
+    fn with_x(&self, new_x: CoordinatePrecision) -> Self {
+        let mut clone = self.clone();
+        clone.x = Some(new_x);
+
+        clone
+    }
+
+    fn with_y(&self, new_y: CoordinatePrecision) -> Self {
+        let mut clone = self.clone();
+        clone.y = Some(new_y);
+
+        clone
+    }
+
+    fn with_x_y(&self, new_x: CoordinatePrecision, new_y: CoordinatePrecision) -> Self {
+        let mut clone = self.clone();
+        clone.x = Some(new_x);
+        clone.y = Some(new_y);
+
+        clone
+    }
+}
+
+/// Wrap each point of the matrix into a cell of the given kind
+pub fn wrap<T: PointTrait, K: Copy>(matrix: &Matrix<T>, kind: K) -> Matrix<Cell<T, K>> {
+    let mut cells: Vec<Cell<T, K>> = vec![];
+    for row in matrix.rows.values() {
+        for point in row.values() {
+            cells.push(Cell::new(point.clone(), kind));
+        }
+    }
+
+    Matrix::from_vec(cells)
+}
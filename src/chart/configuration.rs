@@ -4,10 +4,69 @@ use ui::CoordinatePrecision;
 
 type Row<I: PointTrait> = BTreeMap<CoordinatePrecision, I>;
 
+/// Horizontal line drawn across the canvas at a value (e.g. the session open or an entry price)
+///
+/// Points are drawn on top of the line and its label
+#[derive(Clone)]
+pub struct ReferenceLine {
+    /// Value (see `PointTrait::value()`) at which the line is drawn
+    pub value: f64,
+    pub glyph: String,
+    /// Callback coloring the glyphs and the label
+    pub color: fn(&str) -> String,
+    /// Label drawn at the right end of the line
+    pub label: String,
+}
+
+impl ReferenceLine {
+    pub fn new(value: f64, glyph: &str, color: fn(&str) -> String, label: &str) -> Self {
+        ReferenceLine { value, glyph: glyph.to_string(), color, label: label.to_string() }
+    }
+
+    /// Draw the line's cell in the given column of a canvas `width` columns wide
+    ///
+    /// The label takes the rightmost columns, if it fits
+    pub fn draw_cell(&self, column: usize, width: usize) -> String {
+        let label_width = self.label.chars().count();
+        let label_start = width.saturating_sub(label_width);
+        if label_width <= width && column >= label_start {
+            if let Some(character) = self.label.chars().nth(column - label_start) {
+                return (self.color)(&character.to_string());
+            }
+        }
+
+        (self.color)(&self.glyph)
+    }
+}
+
 pub trait Configuration<T: PointTrait> {
     fn draw_row(&self, row: Option<&Row<T>>, row_number: CoordinatePrecision) -> String;
 
     fn draw_point(&self, point: Option<T>) -> String;
+
+    /// Draw a point with the glyph chosen by the renderer (e.g. a braille pattern)
+    ///
+    /// By default the glyph is returned as is
+    fn draw_glyph(&self, point: Option<T>, glyph: &str) -> String {
+        match point {
+            Some(_) => glyph.to_string(),
+            None => self.draw_point(None),
+        }
+    }
+
+    /// Draw the scala below the chart, given the point (if any) of each column
+    ///
+    /// The result must consist of `height` lines. By default no scala is drawn
+    fn draw_x_scala(&self, _columns: &[Option<T>], _height: CoordinatePrecision) -> String {
+        "".to_string()
+    }
+
+    /// Return the lines drawn across the canvas behind the points
+    ///
+    /// By default there are none
+    fn reference_lines(&self) -> &[ReferenceLine] {
+        &[]
+    }
 }
 
 pub struct CallbackConfiguration<R, P, T: PointTrait>
@@ -59,6 +118,123 @@ impl<R, P, T: PointTrait> Configuration<T> for CallbackConfiguration<R, P, T>
     }
 }
 
+/// Configuration which draws points with a symbol or the glyph chosen by the renderer
+pub struct GlyphCallbackConfiguration<'a, R, G, T: PointTrait>
+    where
+        R: Fn(Option<&Row<T>>, CoordinatePrecision) -> String,
+        G: Fn(Option<T>, &str) -> String {
+    pub symbol: &'a str,
+    pub draw_row: R,
+    pub draw_glyph: G,
+    _use_t: Option<T>,
+}
+
+impl<'a, R, G, T: PointTrait> GlyphCallbackConfiguration<'a, R, G, T>
+    where
+        R: Fn(Option<&Row<T>>, CoordinatePrecision) -> String,
+        G: Fn(Option<T>, &str) -> String
+{
+    pub fn new(symbol: &'a str, draw_row: R, draw_glyph: G) -> Self {
+        GlyphCallbackConfiguration {
+            symbol,
+            draw_row,
+            draw_glyph,
+            _use_t: None,
+        }
+    }
+}
+
+impl<'a, R, G, T: PointTrait> Configuration<T> for GlyphCallbackConfiguration<'a, R, G, T>
+    where R: Fn(Option<&Row<T>>, CoordinatePrecision) -> String,
+          G: Fn(Option<T>, &str) -> String {
+    fn draw_row(&self, row: Option<&Row<T>>, row_number: CoordinatePrecision) -> String {
+        let callback = &self.draw_row;
+        callback(row, row_number)
+    }
+
+    fn draw_point(&self, point: Option<T>) -> String {
+        let callback = &self.draw_glyph;
+        callback(point, self.symbol)
+    }
+
+    fn draw_glyph(&self, point: Option<T>, glyph: &str) -> String {
+        let callback = &self.draw_glyph;
+        callback(point, glyph)
+    }
+}
+
+/// Configuration which adds an x-axis scala to another configuration
+pub struct XScalaConfiguration<'c, X, T: PointTrait + 'c>
+    where X: Fn(&[Option<T>], CoordinatePrecision) -> String {
+    pub inner: &'c dyn Configuration<T>,
+    pub draw_x_scala: X,
+}
+
+impl<'c, X, T: PointTrait + 'c> XScalaConfiguration<'c, X, T>
+    where X: Fn(&[Option<T>], CoordinatePrecision) -> String {
+    pub fn new(inner: &'c dyn Configuration<T>, draw_x_scala: X) -> Self {
+        XScalaConfiguration { inner, draw_x_scala }
+    }
+}
+
+impl<'c, X, T: PointTrait + 'c> Configuration<T> for XScalaConfiguration<'c, X, T>
+    where X: Fn(&[Option<T>], CoordinatePrecision) -> String {
+    fn draw_row(&self, row: Option<&Row<T>>, row_number: CoordinatePrecision) -> String {
+        self.inner.draw_row(row, row_number)
+    }
+
+    fn draw_point(&self, point: Option<T>) -> String {
+        self.inner.draw_point(point)
+    }
+
+    fn draw_glyph(&self, point: Option<T>, glyph: &str) -> String {
+        self.inner.draw_glyph(point, glyph)
+    }
+
+    fn draw_x_scala(&self, columns: &[Option<T>], height: CoordinatePrecision) -> String {
+        let callback = &self.draw_x_scala;
+        callback(columns, height)
+    }
+
+    fn reference_lines(&self) -> &[ReferenceLine] {
+        self.inner.reference_lines()
+    }
+}
+
+/// Configuration which adds reference lines to another configuration
+pub struct ReferenceLineConfiguration<'c, T: PointTrait + 'c> {
+    pub inner: &'c dyn Configuration<T>,
+    pub lines: Vec<ReferenceLine>,
+}
+
+impl<'c, T: PointTrait + 'c> ReferenceLineConfiguration<'c, T> {
+    pub fn new(inner: &'c dyn Configuration<T>, lines: Vec<ReferenceLine>) -> Self {
+        ReferenceLineConfiguration { inner, lines }
+    }
+}
+
+impl<'c, T: PointTrait + 'c> Configuration<T> for ReferenceLineConfiguration<'c, T> {
+    fn draw_row(&self, row: Option<&Row<T>>, row_number: CoordinatePrecision) -> String {
+        self.inner.draw_row(row, row_number)
+    }
+
+    fn draw_point(&self, point: Option<T>) -> String {
+        self.inner.draw_point(point)
+    }
+
+    fn draw_glyph(&self, point: Option<T>, glyph: &str) -> String {
+        self.inner.draw_glyph(point, glyph)
+    }
+
+    fn draw_x_scala(&self, columns: &[Option<T>], height: CoordinatePrecision) -> String {
+        self.inner.draw_x_scala(columns, height)
+    }
+
+    fn reference_lines(&self) -> &[ReferenceLine] {
+        &self.lines
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -90,5 +266,70 @@ mod tests {
         assert_eq!("102", config.draw_row(Some(&row), 102));
         assert_eq!("None", config.draw_point(None));
         assert_eq!("10x20", config.draw_point(Some(Point {})));
+        assert_eq!("g", config.draw_glyph(Some(Point {}), "g"));
+        assert_eq!("None", config.draw_glyph(None, "g"));
+    }
+
+    #[test]
+    fn glyph_call_test() {
+        let config = GlyphCallbackConfiguration::new(
+            "*",
+            |_, r| format!("{}", r),
+            |p: Option<Point>, glyph: &str| match p {
+                Some(_) => format!("<{}>", glyph),
+                None => " ".to_string()
+            },
+        );
+
+        let mut row = Row::new();
+        row.insert(102, Point {});
+        assert_eq!("102", config.draw_row(Some(&row), 102));
+        assert_eq!(" ", config.draw_point(None));
+        assert_eq!("<*>", config.draw_point(Some(Point {})));
+        assert_eq!("<⠁>", config.draw_glyph(Some(Point {}), "⠁"));
+        assert_eq!("", config.draw_x_scala(&[Some(Point {})], 1));
+    }
+
+    #[test]
+    fn x_scala_call_test() {
+        let inner = CallbackConfiguration::new(
+            |_, r| format!("{}", r),
+            |p: Option<Point>| match p {
+                Some(_) => "x".to_string(),
+                None => " ".to_string()
+            },
+        );
+        let config = XScalaConfiguration::new(
+            &inner,
+            |columns: &[Option<Point>], height| format!("{} columns, {} lines", columns.len(), height),
+        );
+
+        assert_eq!("102", config.draw_row(None, 102));
+        assert_eq!("x", config.draw_point(Some(Point {})));
+        assert_eq!("2 columns, 1 lines", config.draw_x_scala(&[Some(Point {}), None], 1));
+    }
+
+    #[test]
+    fn reference_line_call_test() {
+        let inner = CallbackConfiguration::new(
+            |_, r| format!("{}", r),
+            |p: Option<Point>| match p {
+                Some(_) => "x".to_string(),
+                None => " ".to_string()
+            },
+        );
+        let line = ReferenceLine::new(5.0, "-", |text| format!("<{}>", text), "ab");
+        let config = ReferenceLineConfiguration::new(&inner, vec![line]);
+
+        assert_eq!("102", config.draw_row(None, 102));
+        assert_eq!("x", config.draw_point(Some(Point {})));
+        assert_eq!(1, config.reference_lines().len());
+        assert!(inner.reference_lines().is_empty());
+
+        let line = &config.reference_lines()[0];
+        let cells: Vec<String> = (0..4).map(|column| line.draw_cell(column, 4)).collect();
+        assert_eq!("<-><-><a><b>", cells.concat());
+        // Labels wider than the canvas are left out
+        assert_eq!("<->", line.draw_cell(0, 1));
     }
 }
@@ -0,0 +1,97 @@
+use std::collections::BTreeMap;
+use matrix::Matrix;
+use matrix::PointTrait;
+use ui::CoordinatePrecision;
+use super::cell::Cell;
+
+/// Blocks for the top of a bar, from empty up to seven eighths of a cell
+const BLOCK_EIGHTHS: [&str; 8] = [" ", "\u{2581}", "\u{2582}", "\u{2583}", "\u{2584}", "\u{2585}", "\u{2586}", "\u{2587}"];
+
+/// Part of a histogram bar a cell belongs to
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum HistogramPart {
+    /// A completely filled cell
+    Bar,
+    /// The topmost cell of a bar, filled by the given number of eighths
+    Top(u8),
+}
+
+impl HistogramPart {
+    pub fn glyph(self) -> &'static str {
+        match self {
+            HistogramPart::Bar => super::BLOCK_FULL,
+            HistogramPart::Top(eighths) => BLOCK_EIGHTHS[eighths.min(7) as usize],
+        }
+    }
+}
+
+pub type HistogramCell<T> = Cell<T, HistogramPart>;
+
+/// Build a bar for every column holding points
+///
+/// The bars start at zero and reach up to the highest value of their column, with the highest bar
+/// filling all `height` rows. Every column keeps at least an empty top cell, so the columns of the
+/// histogram stay aligned with the ones of the points
+pub fn build<T: PointTrait>(points: &[T], height: CoordinatePrecision) -> Matrix<HistogramCell<T>> {
+    let mut columns: BTreeMap<CoordinatePrecision, T> = BTreeMap::new();
+    for point in points {
+        let is_higher = columns.get(&point.x()).is_none_or(|highest| point.value() > highest.value());
+        if is_higher {
+            columns.insert(point.x(), point.clone());
+        }
+    }
+
+    let max = columns.values().map(|p| p.value()).fold(0.0, f64::max);
+    let mut cells: Vec<HistogramCell<T>> = vec![];
+    for point in columns.values() {
+        let eighths = if max > 0.0 {
+            (point.value().max(0.0) / max * f64::from(height) * 8.0).round() as usize
+        } else {
+            0
+        };
+        let full_rows = (eighths / 8) as CoordinatePrecision;
+        for y in 0..full_rows {
+            cells.push(Cell::new(point.clone(), HistogramPart::Bar).with_y(y));
+        }
+        if eighths % 8 > 0 || full_rows == 0 {
+            cells.push(Cell::new(point.clone(), HistogramPart::Top((eighths % 8) as u8)).with_y(full_rows));
+        }
+    }
+
+    Matrix::from_vec(cells)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use point::Point;
+
+    #[test]
+    fn build_test() {
+        // The values of the points are their `y`
+        let points = vec![Point::new(0, 8), Point::new(1, 0), Point::new(2, 2), Point::new(2, 5), Point::new(3, 6)];
+        let matrix = build(&points, 2);
+
+        // The highest bar fills both rows
+        assert_eq!(HistogramPart::Bar, matrix.get(0, 0).unwrap().kind);
+        assert_eq!(HistogramPart::Bar, matrix.get(1, 0).unwrap().kind);
+        // Columns without a value keep an empty top cell
+        assert_eq!(HistogramPart::Top(0), matrix.get(0, 1).unwrap().kind);
+        assert!(matrix.get(1, 1).is_none());
+        // The highest value of a column is drawn
+        assert_eq!(HistogramPart::Bar, matrix.get(0, 2).unwrap().kind);
+        assert_eq!(HistogramPart::Top(2), matrix.get(1, 2).unwrap().kind);
+        assert_eq!(5.0, matrix.get(1, 2).unwrap().value());
+        assert_eq!(HistogramPart::Top(4), matrix.get(1, 3).unwrap().kind);
+    }
+
+    #[test]
+    fn build_without_values_test() {
+        let matrix = build(&[Point::new(5, 0), Point::new(6, 0)], 3);
+
+        assert_eq!(HistogramPart::Top(0), matrix.get(0, 5).unwrap().kind);
+        assert_eq!(HistogramPart::Top(0), matrix.get(0, 6).unwrap().kind);
+        assert_eq!(" ", HistogramPart::Top(0).glyph());
+        assert!(matrix.get(1, 5).is_none());
+    }
+}
@@ -0,0 +1,189 @@
+use std::collections::BTreeMap;
+use matrix::Matrix;
+use matrix::PointTrait;
+use ui::CoordinatePrecision;
+use super::cell::Cell;
+
+/// Shape of a line cell
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum LineSegment {
+    Horizontal,
+    Vertical,
+    /// Enter from the left and leave upwards
+    RiseStart,
+    /// Enter from below and leave to the right
+    RiseEnd,
+    /// Enter from the left and leave downwards
+    FallStart,
+    /// Enter from above and leave to the right
+    FallEnd,
+    /// Enter from the left and continue both upwards and downwards
+    Branch,
+    /// Top of a run which only continues downwards
+    Peak,
+    /// Bottom of a run which only continues upwards
+    Trough,
+}
+
+impl LineSegment {
+    pub fn glyph(self) -> &'static str {
+        match self {
+            LineSegment::Horizontal => "\u{2500}",
+            LineSegment::Vertical => "\u{2502}",
+            LineSegment::RiseStart => "\u{256f}",
+            LineSegment::RiseEnd => "\u{256d}",
+            LineSegment::FallStart => "\u{256e}",
+            LineSegment::FallEnd => "\u{2570}",
+            LineSegment::Branch => "\u{2524}",
+            LineSegment::Peak => "\u{2577}",
+            LineSegment::Trough => "\u{2575}",
+        }
+    }
+}
+
+/// Cell of a line chart holding the point of its column and the segment to draw
+pub type LineCell<T> = Cell<T, LineSegment>;
+
+/// Connect consecutive columns of the matrix with line cells
+///
+/// If several points share a column, the line runs through all rows from the lowest to the highest of them,
+/// so spikes in both directions stay visible. It enters the column at the row it left the previous one and
+/// leaves it at the end farther away from there. Empty columns between two points are bridged with a
+/// horizontal segment at the height of the previous point.
+pub fn connect<T: PointTrait>(matrix: &Matrix<T>) -> Matrix<LineCell<T>> {
+    let mut columns: BTreeMap<CoordinatePrecision, Vec<T>> = BTreeMap::new();
+    for row in matrix.rows.values() {
+        for (x, point) in row {
+            columns.entry(*x).or_default().push(point.clone());
+        }
+    }
+
+    let mut cells: Vec<LineCell<T>> = vec![];
+    let mut previous: Option<(CoordinatePrecision, CoordinatePrecision)> = None;
+    for (x, points) in &columns {
+        // The points are collected row by row, so they are already ordered by `y`
+        let (lowest, highest) = (&points[0], &points[points.len() - 1]);
+        let (low, high) = (lowest.y(), highest.y());
+        let x = *x;
+        // The line leaves at the end farther away from where it enters
+        let entry = previous.map_or(low, |(_, previous_y)| previous_y);
+        let exit = if high.abs_diff(entry) > entry.abs_diff(low) { highest } else { lowest };
+        let exit_y = exit.y();
+        let cell = |segment: LineSegment, cell_y: CoordinatePrecision| {
+            let point = if cell_y == low { lowest } else if cell_y == high { highest } else { exit };
+
+            Cell::new(point.with_y(cell_y), segment)
+        };
+
+        if let Some((previous_x, previous_y)) = previous {
+            for gap_x in (previous_x + 1)..x {
+                cells.push(cell(LineSegment::Horizontal, previous_y).with_x(gap_x));
+            }
+        }
+        for run_y in entry.min(low)..=entry.max(high) {
+            let segment = if run_y == exit_y && run_y == entry {
+                LineSegment::Horizontal
+            } else if run_y == exit_y && exit_y > entry {
+                LineSegment::RiseEnd
+            } else if run_y == exit_y {
+                LineSegment::FallEnd
+            } else if run_y == entry && previous.is_some() {
+                // The run may reach past the entry on the side away from the exit
+                match (exit_y > entry, low < entry && high > entry) {
+                    (_, true) => LineSegment::Branch,
+                    (true, false) => LineSegment::RiseStart,
+                    (false, false) => LineSegment::FallStart,
+                }
+            } else if run_y == low && (low < entry || previous.is_none()) {
+                LineSegment::Trough
+            } else if run_y == high && high > entry {
+                LineSegment::Peak
+            } else {
+                LineSegment::Vertical
+            };
+            cells.push(cell(segment, run_y));
+        }
+        previous = Some((x, exit_y));
+    }
+
+    Matrix::from_vec(cells)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use point::Point;
+
+    fn draw(matrix: &Matrix<LineCell<Point>>, width: CoordinatePrecision, height: CoordinatePrecision) -> String {
+        let mut buffer = String::new();
+        for y in (0..height).rev() {
+            for x in 0..width {
+                match matrix.get(y, x) {
+                    Some(cell) => buffer.push_str(cell.kind.glyph()),
+                    None => buffer.push(' '),
+                }
+            }
+            buffer.push('\n');
+        }
+
+        buffer
+    }
+
+    #[test]
+    fn connect_test() {
+        let matrix = Matrix::from_vec(vec![
+            Point::new(0, 0),
+            Point::new(1, 3),
+            Point::new(2, 3),
+            Point::new(3, 1),
+            Point::new(5, 2),
+        ]);
+
+        assert_eq!(
+            " ╭─╮  \n │ │ ╭\n │ ╰─╯\n─╯    \n",
+            draw(&connect(&matrix), 6, 4)
+        );
+    }
+
+    #[test]
+    fn connect_keeps_point_test() {
+        let matrix = Matrix::from_vec(vec![Point::new(0, 1), Point::new(1, 0)]);
+        let cells = connect(&matrix);
+
+        assert_eq!(1, cells.get(0, 1).unwrap().point.x);
+        assert_eq!(0, cells.get(0, 1).unwrap().point.y);
+        assert_eq!(LineSegment::FallStart, cells.get(1, 1).unwrap().kind);
+    }
+
+    #[test]
+    fn connect_shared_column_test() {
+        let matrix = Matrix::from_vec(vec![
+            Point::new(0, 0),
+            Point::new(0, 2),
+            Point::new(0, 4),
+        ]);
+
+        // The line runs through all rows of the column
+        assert_eq!("╭\n│\n│\n│\n╵\n", draw(&connect(&matrix), 1, 5));
+    }
+
+    #[test]
+    fn connect_spike_test() {
+        // The line enters each column at the row it left the previous one and leaves at the far end
+        let matrix = Matrix::from_vec(vec![
+            Point::new(0, 2),
+            Point::new(1, 0),
+            Point::new(1, 2),
+            Point::new(2, 1),
+            Point::new(2, 4),
+            Point::new(3, 2),
+            Point::new(4, 0),
+            Point::new(4, 3),
+        ]);
+
+        assert_eq!(
+            "  ╭╮ \n  ││╷\n─╮│╰┤\n ││ │\n ╰╯ ╰\n",
+            draw(&connect(&matrix), 5, 5)
+        );
+    }
+}
@@ -2,23 +2,45 @@ pub mod mode;
 pub mod point_drawing;
 pub mod configuration;
 pub mod padding;
+pub mod x_axis;
+pub mod y_axis;
+pub mod y_range;
+pub mod style;
+pub mod candlestick;
+pub mod cell;
+pub mod line;
+pub mod area;
+pub mod renderer;
+pub mod series;
+pub mod histogram;
 mod canvas;
+mod sub_cell_canvas;
 mod transform;
 
+use std::collections::BTreeMap;
 use term_size;
 pub use self::mode::Mode;
+pub use self::x_axis::XAxis;
+pub use self::y_axis::YAxis;
+pub use self::y_range::YRange;
+pub use self::style::Style;
+pub use self::renderer::Renderer;
+pub use self::transform::ScaleY;
+use self::sub_cell_canvas::SubCellCanvas;
+use self::candlestick::CandlePointTrait;
+use self::line::LineCell;
+use self::area::AreaCell;
+use self::histogram::HistogramCell;
 use self::canvas::Canvas;
+use self::configuration::ReferenceLine;
 use matrix::Matrix;
 use matrix::PointTrait;
 use ui::CoordinatePrecision;
 use ui::screen::DEFAULT_WIDTH;
 use ui::screen::DEFAULT_HEIGHT;
 
-#[allow(unused)]
 pub const BLOCK_FULL: &'static str = "\u{2588}";
-#[allow(unused)]
 pub const BLOCK_UPPER_HALF: &'static str = "\u{2580}";
-#[allow(unused)]
 pub const BLOCK_LOWER_HALF: &'static str = "\u{2584}";
 
 pub struct Chart {
@@ -27,11 +49,28 @@ pub struct Chart {
     _height: CoordinatePrecision,
     pub y_scala_width: CoordinatePrecision,
     pub x_scala_height: CoordinatePrecision,
+    /// Rows of the panels stacked below the points, from top to bottom
+    ///
+    /// The panels share the columns of the points (see `draw_histogram_with_configuration()` and
+    /// `draw_panel_lines_with_configuration()`). If there are panels, the bottom one draws the x-axis
+    /// scala instead of the points
+    pub sub_chart_heights: Vec<CoordinatePrecision>,
+    pub x_axis: XAxis,
+    pub y_axis: YAxis,
+    pub y_range: YRange,
+    pub style: Style,
+    pub renderer: Renderer,
 }
 
 impl Chart {
     pub fn new(width: CoordinatePrecision, height: CoordinatePrecision, x_scala_height: CoordinatePrecision, y_scala_width: CoordinatePrecision, mode: Mode) -> Self {
-        Chart { _width: width, _height: height, mode, x_scala_height, y_scala_width }
+        Chart { _width: width, _height: height, mode, x_scala_height, y_scala_width, sub_chart_heights: vec![], x_axis: XAxis::Index, y_axis: YAxis::Price, y_range: YRange::auto(), style: Style::Points, renderer: Renderer::Cell }
+    }
+
+    /// Set the size of the chart (zero uses the size of the terminal)
+    pub fn set_size(&mut self, width: CoordinatePrecision, height: CoordinatePrecision) {
+        self._width = width;
+        self._height = height;
     }
 
     pub fn width(&self) -> CoordinatePrecision {
@@ -57,20 +96,59 @@ impl Chart {
         }
     }
 
+    /// Return the number of rows left for the points
+    fn points_height(&self) -> CoordinatePrecision {
+        self.height().saturating_sub(self.x_scala_height + self.sub_chart_heights.iter().sum::<CoordinatePrecision>())
+    }
+
+    /// Return the height of the x-axis scala drawn below the points
+    fn points_x_scala_height(&self) -> CoordinatePrecision {
+        if self.sub_chart_heights.is_empty() { self.x_scala_height } else { 0 }
+    }
+
+    /// Return the canvas of the panel with the given index, if the chart has such a panel
+    ///
+    /// Its cells are addressed from the bottom left corner, like the ones returned by
+    /// `transform::place_columns()`
+    fn get_panel_canvas(&self, panel: usize) -> Option<Canvas> {
+        let height = match self.sub_chart_heights.get(panel) {
+            Some(&height) if height > 0 => height,
+            _ => return None,
+        };
+        let x_scala_height = if panel + 1 == self.sub_chart_heights.len() { self.x_scala_height } else { 0 };
+
+        Some(Canvas::new(
+            self.width() - self.y_scala_width,
+            height,
+            padding::Padding::new(0, 0, 1, 1),
+        ).with_x_scala_height(x_scala_height).with_x_start(Some(0)).with_y_start(Some(0)))
+    }
+
+    /// Return the number of horizontal and vertical points a single cell displays
+    ///
+    /// Sub-cell renderers only apply to the points style
+    pub fn resolution(&self) -> (CoordinatePrecision, CoordinatePrecision) {
+        match self.style {
+            Style::Points => self.renderer.resolution(),
+            _ => (1, 1),
+        }
+    }
+
+    /// Return the number of point columns that fit next to the y-axis scala
+    pub fn point_columns(&self) -> usize {
+        (self.width().saturating_sub(self.y_scala_width) * self.resolution().0) as usize
+    }
+
+    /// Return how the points' values are mapped onto the rows
+    pub fn scale_y<T: PointTrait>(&self, points: &[T]) -> ScaleY {
+        transform::get_scale_y(self, points)
+    }
 
     #[allow(unused)]
     pub fn draw_points<T: PointTrait>(&self, points: Vec<T>) -> String {
-        let matrix = Matrix::from_vec(points);
+        let matrix = transform::quantize(self, &points);
         if let Some(canvas) = self.get_canvas(&matrix) {
-            match self.mode {
-                Mode::Truncate => canvas.draw_points(matrix),
-                Mode::ScaleX => canvas.draw_points(transform::scale_x(self, &matrix)),
-                Mode::ScaleY => canvas.draw_points(transform::scale_y(self, &matrix)),
-                Mode::Scale => canvas.draw_points(transform::scale(self, &matrix)),
-                Mode::ScaleDownX => canvas.draw_points(transform::scale_down_x(self, &matrix)),
-                Mode::ScaleDownY => canvas.draw_points(transform::scale_down_y(self, &matrix)),
-                Mode::ScaleDown => canvas.draw_points(transform::scale_down(self, &matrix)),
-            }
+            canvas.draw_points(matrix)
         } else {
             "".to_string()
         }
@@ -78,35 +156,131 @@ impl Chart {
 
     #[allow(unused)]
     pub fn draw_points_with_configuration<T: PointTrait>(&self, points: Vec<T>, conf: &configuration::Configuration<T>) -> String {
-        let matrix = Matrix::from_vec(points);
+        let matrix = transform::quantize(self, &points);
+        let reference_rows = self.get_reference_rows(&points, conf);
+        if self.resolution() != (1, 1) {
+            return self.draw_sub_cell_points_with_configuration(matrix, reference_rows, conf);
+        }
         if let Some(canvas) = self.get_canvas(&matrix) {
-            match self.mode {
-                Mode::Truncate => canvas.draw_points_with_configuration(matrix, conf),
-                Mode::ScaleX => canvas.draw_points_with_configuration(transform::scale_x(self, &matrix), conf),
-                Mode::ScaleY => canvas.draw_points_with_configuration(transform::scale_y(self, &matrix), conf),
-                Mode::Scale => canvas.draw_points_with_configuration(transform::scale(self, &matrix), conf),
-                Mode::ScaleDownX => canvas.draw_points_with_configuration(transform::scale_down_x(self, &matrix), conf),
-                Mode::ScaleDownY => canvas.draw_points_with_configuration(transform::scale_down_y(self, &matrix), conf),
-                Mode::ScaleDown => canvas.draw_points_with_configuration(transform::scale_down(self, &matrix), conf),
-            }
+            canvas.with_reference_rows(reference_rows).draw_points_with_configuration(matrix, conf)
         } else {
             "".to_string()
         }
     }
 
+    /// Draw candles given as open/high/low/close points (see `candlestick::CandlePart`)
+    pub fn draw_candles_with_configuration<T: CandlePointTrait>(&self, points: Vec<T>, conf: &dyn configuration::Configuration<T>) -> String {
+        let matrix = transform::quantize_candles(self, &points);
+        if let Some(canvas) = self.get_canvas(&matrix) {
+            canvas.with_reference_rows(self.get_reference_rows(&points, conf)).draw_candles_with_configuration(matrix, conf)
+        } else {
+            "".to_string()
+        }
+    }
+
+    /// Draw the points connected by a line
+    pub fn draw_line_with_configuration<T: PointTrait>(&self, points: Vec<T>, conf: &dyn configuration::Configuration<LineCell<T>>) -> String {
+        let matrix = transform::quantize(self, &points);
+        if let Some(canvas) = self.get_canvas(&matrix) {
+            canvas.with_reference_rows(self.get_reference_rows(&points, conf)).draw_line_with_configuration(matrix, conf)
+        } else {
+            "".to_string()
+        }
+    }
+
+    /// Draw the points and fill the area below them
+    pub fn draw_area_with_configuration<T: PointTrait>(&self, points: Vec<T>, conf: &dyn configuration::Configuration<AreaCell<T>>) -> String {
+        let matrix = transform::quantize(self, &points);
+        if let Some(canvas) = self.get_canvas(&matrix) {
+            canvas.with_reference_rows(self.get_reference_rows(&points, conf)).draw_area_with_configuration(matrix, conf)
+        } else {
+            "".to_string()
+        }
+    }
+
+    /// Draw the points of several series with a common scale
+    ///
+    /// Where the series overlap, the one given first is drawn
+    pub fn draw_series_with_configuration<T: PointTrait>(&self, series: Vec<Vec<T>>, conf: &dyn configuration::Configuration<series::SeriesCell<T>>) -> String {
+        let matrix = series::overlay(&transform::quantize_series(self, &series));
+        let reference_rows = self.get_reference_rows(&series.concat(), conf);
+        if self.resolution() != (1, 1) {
+            return self.draw_sub_cell_points_with_configuration(matrix, reference_rows, conf);
+        }
+        if let Some(canvas) = self.get_canvas(&matrix) {
+            canvas.with_reference_rows(reference_rows).draw_points_with_configuration(matrix, conf)
+        } else {
+            "".to_string()
+        }
+    }
+
+    /// Draw the points of several series each connected by a line with a common scale
+    ///
+    /// Where the lines overlap, the series given first is drawn
+    pub fn draw_series_lines_with_configuration<T: PointTrait>(&self, series: Vec<Vec<T>>, conf: &dyn configuration::Configuration<LineCell<series::SeriesCell<T>>>) -> String {
+        let matrices = transform::quantize_series(self, &series);
+        if let Some(canvas) = self.get_canvas(&series::overlay(&matrices)) {
+            canvas.with_reference_rows(self.get_reference_rows(&series.concat(), conf)).draw_lines_with_configuration(matrices, conf)
+        } else {
+            "".to_string()
+        }
+    }
+
+    /// Return how values spanning the fixed `y_range` are mapped onto the rows of the panel with the given index
+    ///
+    /// The bounds of the range fall on the bottom and the top row
+    pub fn panel_scale_y(&self, panel: usize, y_range: &YRange) -> ScaleY {
+        let (min, max) = y_range.resolve(0.0, 1.0);
+        let rows = f64::from(self.sub_chart_heights.get(panel).map_or(1, |&height| height.max(1)) - 1);
+
+        ScaleY::Linear { factor: if max > min && rows > 0.0 { rows / (max - min) } else { 1.0 }, origin: min }
+    }
+
+    /// Draw the values of the points as bars in the panel with the given index
+    ///
+    /// The points must have the same `x` coordinates as the ones drawn above, so the columns of both line
+    /// up. The bars start at zero and the highest one fills the panel (see `histogram::build()`)
+    pub fn draw_histogram_with_configuration<T: PointTrait>(&self, panel: usize, points: Vec<T>, conf: &dyn configuration::Configuration<HistogramCell<T>>) -> String {
+        match self.get_panel_canvas(panel) {
+            Some(canvas) if !points.is_empty() => {
+                let matrix = histogram::build(&transform::place_columns(self, &points), self.sub_chart_heights[panel]);
+                canvas.draw_points_with_configuration(matrix, conf)
+            }
+            _ => "".to_string(),
+        }
+    }
+
+    /// Draw the points of several series each connected by a line in the panel with the given index
+    ///
+    /// The panel has its own scale spanning the fixed `y_range`, while the columns line up with the points
+    /// drawn above. Points with a value which is not finite only take up their column, so lines starting
+    /// later than the points stay aligned
+    pub fn draw_panel_lines_with_configuration<T: PointTrait>(&self, panel: usize, series: Vec<Vec<T>>, y_range: YRange, conf: &dyn configuration::Configuration<LineCell<series::SeriesCell<T>>>) -> String {
+        let canvas = match self.get_panel_canvas(panel) {
+            Some(canvas) if series.iter().any(|points| !points.is_empty()) => canvas,
+            _ => return "".to_string(),
+        };
+        let scale_y = self.panel_scale_y(panel, &y_range);
+
+        let mut placed = transform::place_columns(self, &series.concat()).into_iter();
+        let matrices: Vec<Matrix<series::SeriesCell<T>>> = series.iter().enumerate()
+            .map(|(index, points)| {
+                let cells = placed.by_ref().take(points.len())
+                    .filter(|p| p.value().is_finite())
+                    .map(|p| series::SeriesCell::new(p.with_y(scale_y.apply(y_range.clamp(p.value()))), index))
+                    .collect();
+                Matrix::from_vec(cells)
+            })
+            .collect();
+
+        canvas.with_reference_rows(Self::get_reference_rows_for_scale(scale_y, &y_range, conf)).draw_lines_with_configuration(matrices, conf)
+    }
+
     #[allow(unused)]
     pub fn draw_points_with_symbol<T: PointTrait>(&self, points: Vec<T>, symbol: &str) -> String {
-        let matrix = Matrix::from_vec(points);
+        let matrix = transform::quantize(self, &points);
         if let Some(canvas) = self.get_canvas(&matrix) {
-            match self.mode {
-                Mode::Truncate => canvas.draw_points_with_symbol(matrix, symbol),
-                Mode::ScaleX => canvas.draw_points_with_symbol(transform::scale_x(self, &matrix), symbol),
-                Mode::ScaleY => canvas.draw_points_with_symbol(transform::scale_y(self, &matrix), symbol),
-                Mode::Scale => canvas.draw_points_with_symbol(transform::scale(self, &matrix), symbol),
-                Mode::ScaleDownX => canvas.draw_points_with_symbol(transform::scale_down_x(self, &matrix), symbol),
-                Mode::ScaleDownY => canvas.draw_points_with_symbol(transform::scale_down_y(self, &matrix), symbol),
-                Mode::ScaleDown => canvas.draw_points_with_symbol(transform::scale_down(self, &matrix), symbol),
-            }
+            canvas.draw_points_with_symbol(matrix, symbol)
         } else {
             "".to_string()
         }
@@ -114,17 +288,9 @@ impl Chart {
 
     #[allow(unused)]
     pub fn draw_points_with_symbols<T: PointTrait>(&self, points: Vec<T>, point_symbol: &str, placeholder: &str) -> String {
-        let matrix = Matrix::from_vec(points);
+        let matrix = transform::quantize(self, &points);
         if let Some(canvas) = self.get_canvas(&matrix) {
-            match self.mode {
-                Mode::Truncate => canvas.draw_points_with_symbols(matrix, point_symbol, placeholder),
-                Mode::ScaleX => canvas.draw_points_with_symbols(transform::scale_x(self, &matrix), point_symbol, placeholder),
-                Mode::ScaleY => canvas.draw_points_with_symbols(transform::scale_y(self, &matrix), point_symbol, placeholder),
-                Mode::Scale => canvas.draw_points_with_symbols(transform::scale(self, &matrix), point_symbol, placeholder),
-                Mode::ScaleDownX => canvas.draw_points_with_symbols(transform::scale_down_x(self, &matrix), point_symbol, placeholder),
-                Mode::ScaleDownY => canvas.draw_points_with_symbols(transform::scale_down_y(self, &matrix), point_symbol, placeholder),
-                Mode::ScaleDown => canvas.draw_points_with_symbols(transform::scale_down(self, &matrix), point_symbol, placeholder),
-            }
+            canvas.draw_points_with_symbols(matrix, point_symbol, placeholder)
         } else {
             "".to_string()
         }
@@ -133,22 +299,27 @@ impl Chart {
     #[allow(unused)]
     pub fn draw_points_with_callback<F, T: PointTrait>(&self, points: Vec<T>, draw_callback: F) -> String
         where F: Fn(Option<T>) -> String {
-        let matrix = Matrix::from_vec(points);
+        let matrix = transform::quantize(self, &points);
         if let Some(canvas) = self.get_canvas(&matrix) {
-            match self.mode {
-                Mode::Truncate => canvas.draw_points_with_callback(matrix, &draw_callback),
-                Mode::ScaleX => canvas.draw_points_with_callback(transform::scale_x(self, &matrix), &draw_callback),
-                Mode::ScaleY => canvas.draw_points_with_callback(transform::scale_y(self, &matrix), &draw_callback),
-                Mode::Scale => canvas.draw_points_with_callback(transform::scale(self, &matrix), &draw_callback),
-                Mode::ScaleDownX => canvas.draw_points_with_callback(transform::scale_down_x(self, &matrix), &draw_callback),
-                Mode::ScaleDownY => canvas.draw_points_with_callback(transform::scale_down_y(self, &matrix), &draw_callback),
-                Mode::ScaleDown => canvas.draw_points_with_callback(transform::scale_down(self, &matrix), &draw_callback),
-            }
+            canvas.draw_points_with_callback(matrix, &draw_callback)
         } else {
             "".to_string()
         }
     }
 
+    fn draw_sub_cell_points_with_configuration<T: PointTrait>(&self, matrix: Matrix<T>, reference_rows: BTreeMap<CoordinatePrecision, ReferenceLine>, conf: &dyn configuration::Configuration<T>) -> String {
+        if matrix.is_empty() {
+            return "".to_string();
+        }
+        let canvas = SubCellCanvas::new(
+            self.width() - self.y_scala_width,
+            self.points_height(),
+            padding::Padding::new(0, 0, 1, 1),
+            self.renderer,
+        ).with_x_scala_height(self.points_x_scala_height()).with_y_start(self.fixed_y_start()).with_reference_rows(reference_rows);
+        canvas.draw_points_with_configuration(matrix, conf)
+    }
+
     fn get_canvas<T: PointTrait>(&self, point_matrix: &Matrix<T>) -> Option<Canvas> {
         let x_min_option = point_matrix.x_min();
         let y_min_option = point_matrix.y_min();
@@ -158,9 +329,39 @@ impl Chart {
 
         Some(Canvas::new(
             self.width() - self.y_scala_width,
-            self.height() - self.x_scala_height,
+            self.points_height(),
             padding::Padding::new(0, 0, 1, 1),
-        ))
+        ).with_x_scala_height(self.points_x_scala_height()).with_y_start(self.fixed_y_start()))
+    }
+
+    /// Return the reference lines of the configuration by the row they are drawn on
+    ///
+    /// Lines outside of a fixed y-range or below the lowest row are left out. Where several lines fall on
+    /// the same row, the one given first is drawn
+    fn get_reference_rows<T: PointTrait, C: PointTrait>(&self, points: &[T], conf: &dyn configuration::Configuration<C>) -> BTreeMap<CoordinatePrecision, ReferenceLine> {
+        if conf.reference_lines().is_empty() {
+            return BTreeMap::new();
+        }
+
+        Self::get_reference_rows_for_scale(self.scale_y(points), &self.y_range, conf)
+    }
+
+    /// Return the reference lines of the configuration by the row they are drawn on with the given scale
+    fn get_reference_rows_for_scale<C: PointTrait>(scale_y: ScaleY, y_range: &YRange, conf: &dyn configuration::Configuration<C>) -> BTreeMap<CoordinatePrecision, ReferenceLine> {
+        let mut reference_rows = BTreeMap::new();
+        for line in conf.reference_lines() {
+            if !line.value.is_finite() || y_range.marker(line.value).is_some() || line.value < scale_y.invert(0.0) {
+                continue;
+            }
+            reference_rows.entry(scale_y.apply(line.value)).or_insert_with(|| line.clone());
+        }
+
+        reference_rows
+    }
+
+    /// Return the lowest row if the y-range is fixed, so the window does not follow the points
+    fn fixed_y_start(&self) -> Option<CoordinatePrecision> {
+        if self.y_range.is_auto() { None } else { Some(0) }
     }
 }
 
@@ -327,6 +528,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn draw_points_with_braille_test() {
+        let mut chart = Chart::new(2, 1, 0, 0, Mode::Truncate);
+        chart.renderer = Renderer::Braille;
+        let conf = configuration::CallbackConfiguration::new(
+            |_: Option<&::matrix::Row<Point>>, _: CoordinatePrecision| "|".to_string(),
+            |point: Option<Point>| match point {
+                Some(_) => "x".to_string(),
+                None => " ".to_string(),
+            },
+        );
+
+        assert_eq!(4, chart.point_columns());
+        assert_eq!(
+            "|\u{2860}\u{2802}\n",
+            chart.draw_points_with_configuration(vec![Point::new(0, 0), Point::new(1, 1), Point::new(2, 2)], &conf)
+        );
+    }
+
     #[test]
     fn scale_not_needed_test() {
         let chart_with_truncate = Chart::new(6, 6, 0, 0, Mode::Truncate).draw_points_with_symbols(
@@ -423,6 +643,206 @@ mod tests {
         );
     }
 
+    #[test]
+    fn log_test() {
+        let points = vec![
+            Point::new(0, 1),
+            Point::new(1, 10),
+            Point::new(2, 100),
+            Point::new(3, 1000),
+        ];
+
+        assert_eq!(
+            "___x\n__x_\n_x__\nx___\n",
+            Chart::new(4, 4, 0, 0, Mode::Log).draw_points_with_symbols(points.clone(), "x", "_")
+        );
+        assert_eq!(ScaleY::Logarithmic { factor: 3.0 / 1000f64.ln(), origin: 0.0, offset: 0.0 }, Chart::new(4, 4, 0, 0, Mode::Log).scale_y(&points));
+        assert_eq!(ScaleY::Linear { factor: 1.0, origin: 1.0 }, Chart::new(4, 4, 0, 0, Mode::Truncate).scale_y(&points));
+    }
+
+    #[test]
+    fn log_percent_test() {
+        // Changes of -75%, -50%, 0%, +100% and +300% relative to the anchor
+        let rate = ::rate::Rate::from_price(::rate::Currency::bitcoin(), "USD", "1".parse().unwrap());
+        let points: Vec<::rate::Rate> = [-7_500.0, -5_000.0, 0.0, 10_000.0, 30_000.0].iter().enumerate()
+            .map(|(x, value)| rate.with_x(x as CoordinatePrecision).with_value(*value))
+            .collect();
+        let mut chart = Chart::new(5, 5, 0, 0, Mode::Log);
+        chart.y_axis = YAxis::PercentChange(None);
+
+        // Drops are scaled like rises by their ratio to the anchor (1/4, 1/2, 1, 2 and 4)
+        let matrix = transform::quantize(&chart, &points);
+        let rows: Vec<CoordinatePrecision> = (0..5).map(|x| (0..5).find(|&y| matrix.get(y, x).is_some()).unwrap()).collect();
+        assert_eq!(vec![0, 1, 2, 3, 4], rows);
+        assert!(chart.scale_y(&points).invert(2.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn y_range_test() {
+        let points = vec![
+            Point::new(0, 10),
+            Point::new(1, 20),
+            Point::new(2, 50),     // Will be moved onto the top row
+            Point::new(3, 15),
+        ];
+        let mut chart = Chart::new(4, 4, 0, 0, Mode::Truncate);
+        chart.y_range = YRange::new(Some(0.0), Some(30.0)).unwrap();
+
+        // The bottom row stays empty, as the range starts below the points
+        assert_eq!("__x_\n_x__\nx__x\n____\n", chart.draw_points_with_symbols(points.clone(), "x", "_"));
+        assert_eq!(ScaleY::Linear { factor: 0.1, origin: 0.0 }, chart.scale_y(&points));
+    }
+
+    #[test]
+    fn downsample_test() {
+        let points = vec![
+            Point::new(0, 0),
+            Point::new(1, 5),      // Will be dropped
+            Point::new(2, 9),
+            Point::new(3, 4),      // Will be dropped
+            Point::new(4, 6),
+            Point::new(5, 3),
+        ];
+        let matrix = transform::quantize(&Chart::new(2, 10, 0, 0, Mode::ScaleDown), &points);
+
+        assert!(matrix.get(0, 0).is_some());
+        assert!(matrix.get(9, 0).is_some());
+        assert!(matrix.get(5, 0).is_none());
+        assert!(matrix.get(6, 1).is_some());
+        assert!(matrix.get(3, 1).is_some());
+        assert!(matrix.get(4, 1).is_none());
+    }
+
+    #[test]
+    fn draw_candles_test() {
+        let rate = |price: &str| ::rate::Rate::from_price(::rate::Currency::bitcoin(), "USD", price.parse().unwrap());
+        let mut candle = ::rate::Candle::new(&rate("100"), 60);
+        candle.update(&rate("104"));
+        candle.update(&rate("97"));
+        candle.update(&rate("99"));
+        let conf = configuration::CallbackConfiguration::new(
+            |_: Option<&::matrix::Row<::rate::Candle>>, _: CoordinatePrecision| "".to_string(),
+            |point: Option<::rate::Candle>| match point.map(|point| point.part()) {
+                Some(candlestick::CandlePart::Body) => "#".to_string(),
+                Some(_) => "|".to_string(),
+                None => "_".to_string(),
+            },
+        );
+
+        // All four prices of the candle share a column, but none of them may be dropped
+        for mode in [Mode::Truncate, Mode::Scale, Mode::ScaleDown, Mode::Log] {
+            assert_eq!(
+                "|\n|\n|\n|\n#\n#\n|\n|\n",
+                Chart::new(1, 8, 0, 0, mode).draw_candles_with_configuration(candle.to_points(), &conf),
+                "{:?}",
+                mode
+            );
+        }
+    }
+
+    #[test]
+    fn draw_line_spike_test() {
+        // Four points share each column, the second column holds a downward spike
+        let values = [3, 3, 3, 3, 3, 0, 3, 3, 3, 3, 3, 3];
+        let points: Vec<Point> = values.iter().enumerate().map(|(x, y)| Point::new(x as CoordinatePrecision, *y)).collect();
+        let conf = configuration::CallbackConfiguration::new(
+            |_: Option<&::matrix::Row<LineCell<Point>>>, _: CoordinatePrecision| "".to_string(),
+            |cell: Option<LineCell<Point>>| cell.map_or(" ".to_string(), |cell| cell.kind.glyph().to_string()),
+        );
+
+        assert_eq!(
+            "─╮╭\n ││\n ││\n ╰╯\n",
+            Chart::new(3, 4, 0, 0, Mode::ScaleDown).draw_line_with_configuration(points, &conf)
+        );
+    }
+
+    #[test]
+    fn draw_series_test() {
+        let chart = Chart::new(4, 4, 0, 0, Mode::Scale);
+        let series = vec![
+            vec![Point::new(0, 0), Point::new(1, 1), Point::new(2, 2), Point::new(3, 3)],
+            vec![Point::new(0, 3), Point::new(1, 1), Point::new(2, 0)],
+        ];
+        let conf = configuration::CallbackConfiguration::new(
+            |_: Option<&::matrix::Row<series::SeriesCell<Point>>>, _: CoordinatePrecision| "".to_string(),
+            |cell: Option<series::SeriesCell<Point>>| match cell {
+                Some(cell) => cell.kind.to_string(),
+                None => "_".to_string(),
+            },
+        );
+
+        // The first series wins where both overlap
+        assert_eq!("1__0\n__0_\n_0__\n0_1_\n", chart.draw_series_with_configuration(series, &conf));
+    }
+
+    #[test]
+    fn draw_reference_lines_test() {
+        let points = vec![Point::new(0, 0), Point::new(1, 1), Point::new(2, 2), Point::new(3, 3)];
+        let inner = configuration::CallbackConfiguration::new(
+            |_: Option<&::matrix::Row<Point>>, _: CoordinatePrecision| "".to_string(),
+            |point: Option<Point>| match point {
+                Some(_) => "x".to_string(),
+                None => "_".to_string(),
+            },
+        );
+        let lines = vec![
+            ReferenceLine::new(2.0, "-", |text| text.to_string(), "L"),
+            ReferenceLine::new(2.0, "=", |text| text.to_string(), ""),
+            ReferenceLine::new(-1.0, "=", |text| text.to_string(), ""),
+        ];
+        let conf = configuration::ReferenceLineConfiguration::new(&inner, lines);
+
+        // Points are drawn on top of the line and the line given first wins
+        assert_eq!("___x\n--xL\n_x__\nx___\n", Chart::new(4, 4, 0, 0, Mode::Scale).draw_points_with_configuration(points.clone(), &conf));
+
+        // Lines outside of a fixed y-range are left out
+        let mut chart = Chart::new(4, 4, 0, 0, Mode::Scale);
+        chart.y_range = YRange::new(Some(0.0), Some(1.0)).unwrap();
+        assert!(!chart.draw_points_with_configuration(points, &conf).contains('L'));
+    }
+
+    #[test]
+    fn draw_histogram_test() {
+        let mut chart = Chart::new(4, 5, 0, 0, Mode::Scale);
+        chart.sub_chart_heights = vec![2];
+        let points = vec![Point::new(0, 0), Point::new(1, 1), Point::new(2, 2), Point::new(3, 3)];
+        let conf = configuration::CallbackConfiguration::new(
+            |_: Option<&::matrix::Row<HistogramCell<Point>>>, _: CoordinatePrecision| "".to_string(),
+            |cell: Option<HistogramCell<Point>>| match cell.map(|cell| cell.kind) {
+                Some(histogram::HistogramPart::Bar) => "#".to_string(),
+                Some(histogram::HistogramPart::Top(eighths)) => eighths.to_string(),
+                None => "_".to_string(),
+            },
+        );
+
+        // The points take the rows left above the sub-chart
+        assert_eq!("___x\n__x_\nxx__\n", chart.draw_points_with_symbols(points.clone(), "x", "_"));
+        assert_eq!("__3#\n05##\n", chart.draw_histogram_with_configuration(0, points, &conf));
+    }
+
+    #[test]
+    fn draw_panel_lines_test() {
+        let mut chart = Chart::new(4, 6, 0, 0, Mode::Scale);
+        chart.sub_chart_heights = vec![1, 3];
+        let series = vec![
+            vec![Point::new(0, 0), Point::new(1, 1), Point::new(2, 2)],
+            vec![Point::new(1, 2), Point::new(2, 0)],
+        ];
+        let inner = configuration::CallbackConfiguration::new(
+            |_: Option<&::matrix::Row<LineCell<series::SeriesCell<Point>>>>, _: CoordinatePrecision| "".to_string(),
+            |cell: Option<LineCell<series::SeriesCell<Point>>>| match cell {
+                Some(cell) => cell.point.kind.to_string(),
+                None => "_".to_string(),
+            },
+        );
+        let conf = configuration::ReferenceLineConfiguration::new(&inner, vec![ReferenceLine::new(1.0, "-", |text| text.to_string(), "")]);
+        let y_range = YRange::new(Some(0.0), Some(2.0)).unwrap();
+
+        // The later series lines up with the first one and the first series wins where both overlap
+        assert_eq!("_10_\n-00-\n001_\n", chart.draw_panel_lines_with_configuration(1, series.clone(), y_range, &conf));
+        assert_eq!("", chart.draw_panel_lines_with_configuration(2, series, y_range, &conf));
+    }
+
     #[test]
     fn do_not_scale_up_test() {
         assert_eq!(
@@ -1,4 +1,4 @@
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub enum Mode {
     Truncate,
     Scale,
@@ -7,6 +7,8 @@ pub enum Mode {
     ScaleDown,
     ScaleDownX,
     ScaleDownY,
+    /// Scale down horizontally and map the prices logarithmically onto the height
+    Log,
 }
 
 impl Mode {
@@ -19,6 +21,7 @@ impl Mode {
             "scale-down" => Ok(Mode::ScaleDown),
             "scale-down-x" => Ok(Mode::ScaleDownX),
             "scale-down-y" => Ok(Mode::ScaleDownY),
+            "log" => Ok(Mode::Log),
             _ => Err(()),
         }
     }
@@ -40,5 +43,6 @@ mod tests {
         assert_eq!(Mode::ScaleDownX, Mode::from_str("scale-down-x").unwrap());
         assert_eq!(Mode::ScaleDownY, Mode::from_str("scale-down-y").unwrap());
         assert_eq!(Mode::Truncate, Mode::from_str("truncate").unwrap());
+        assert_eq!(Mode::Log, Mode::from_str("log").unwrap());
     }
 }
\ No newline at end of file
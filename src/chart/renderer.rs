@@ -0,0 +1,106 @@
+use std::char;
+use ui::CoordinatePrecision;
+
+/// First character of the Unicode braille pattern block (no dots set)
+const BRAILLE_BLANK: u32 = 0x2800;
+
+/// Dot bits of a braille cell, indexed by the column and the row (counted from the top)
+const BRAILLE_DOTS: [[u32; 4]; 2] = [
+    [0x01, 0x02, 0x04, 0x40],
+    [0x08, 0x10, 0x20, 0x80],
+];
+
+/// Defines how points are mapped onto terminal cells
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Renderer {
+    /// One point per cell
+    Cell,
+    /// Upper and lower half blocks with 1×2 dots per cell
+    HalfBlock,
+    /// Braille patterns with 2×4 dots per cell
+    Braille,
+}
+
+impl Renderer {
+    pub fn from_str(renderer: &str) -> Result<Self, ()> {
+        match renderer {
+            "cell" => Ok(Renderer::Cell),
+            "half-block" => Ok(Renderer::HalfBlock),
+            "braille" => Ok(Renderer::Braille),
+            _ => Err(()),
+        }
+    }
+
+    /// Return the number of horizontal and vertical points a single cell can display
+    pub fn resolution(self) -> (CoordinatePrecision, CoordinatePrecision) {
+        match self {
+            Renderer::Cell => (1, 1),
+            Renderer::HalfBlock => (1, 2),
+            Renderer::Braille => (2, 4),
+        }
+    }
+
+    /// Return the bit for the dot in the given column and row (counted from the top) of a cell
+    pub fn dot(column: u32, row_from_top: u32, resolution_y: u32) -> u32 {
+        1 << (column * resolution_y + row_from_top)
+    }
+
+    /// Return the glyph for a cell with the given dots (see `Renderer::dot()`) set
+    pub fn glyph(self, dots: u32) -> String {
+        match self {
+            Renderer::Cell => super::BLOCK_FULL.to_string(),
+            Renderer::HalfBlock => match dots & 0b11 {
+                0b01 => super::BLOCK_UPPER_HALF.to_string(),
+                0b10 => super::BLOCK_LOWER_HALF.to_string(),
+                0b11 => super::BLOCK_FULL.to_string(),
+                _ => " ".to_string(),
+            },
+            Renderer::Braille => {
+                let mut pattern = 0;
+                for (column, column_dots) in BRAILLE_DOTS.iter().enumerate() {
+                    for (row_from_top, dot) in column_dots.iter().enumerate() {
+                        if dots & Self::dot(column as u32, row_from_top as u32, 4) != 0 {
+                            pattern |= dot;
+                        }
+                    }
+                }
+
+                char::from_u32(BRAILLE_BLANK + pattern).unwrap_or(' ').to_string()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_str_test() {
+        assert!(Renderer::from_str("invalid").is_err());
+        assert!(Renderer::from_str("").is_err());
+
+        assert_eq!(Renderer::Cell, Renderer::from_str("cell").unwrap());
+        assert_eq!(Renderer::HalfBlock, Renderer::from_str("half-block").unwrap());
+        assert_eq!(Renderer::Braille, Renderer::from_str("braille").unwrap());
+    }
+
+    #[test]
+    fn glyph_test() {
+        assert_eq!("\u{2800}", Renderer::Braille.glyph(0));
+        assert_eq!("\u{28ff}", Renderer::Braille.glyph(0xff));
+        assert_eq!("\u{2801}", Renderer::Braille.glyph(Renderer::dot(0, 0, 4)));
+        assert_eq!("\u{2880}", Renderer::Braille.glyph(Renderer::dot(1, 3, 4)));
+
+        assert_eq!(super::super::BLOCK_UPPER_HALF, Renderer::HalfBlock.glyph(Renderer::dot(0, 0, 2)));
+        assert_eq!(super::super::BLOCK_LOWER_HALF, Renderer::HalfBlock.glyph(Renderer::dot(0, 1, 2)));
+        assert_eq!(super::super::BLOCK_FULL, Renderer::HalfBlock.glyph(0b11));
+    }
+
+    #[test]
+    fn resolution_test() {
+        assert_eq!((1, 1), Renderer::Cell.resolution());
+        assert_eq!((1, 2), Renderer::HalfBlock.resolution());
+        assert_eq!((2, 4), Renderer::Braille.resolution());
+    }
+}
@@ -0,0 +1,41 @@
+use matrix::Matrix;
+use matrix::PointTrait;
+use super::cell::Cell;
+
+/// Cell holding a point of one of several series drawn in the same chart
+///
+/// The kind is the index of the series the point belongs to
+pub type SeriesCell<T> = Cell<T, usize>;
+
+/// Merge the matrices into one
+///
+/// Where the matrices overlap, the point of the first matrix is kept, so the series given first are
+/// drawn on top of the others
+pub fn overlay<T: PointTrait>(matrices: &[Matrix<T>]) -> Matrix<T> {
+    let mut points: Vec<T> = vec![];
+    for matrix in matrices.iter().rev() {
+        for row in matrix.rows.values() {
+            points.extend(row.values().cloned());
+        }
+    }
+
+    // Later points replace earlier ones in the same cell
+    Matrix::from_vec(points)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use point::Point;
+
+    #[test]
+    fn overlay_test() {
+        let first = Matrix::from_vec(vec![Cell::new(Point::new(0, 0), 0), Cell::new(Point::new(1, 1), 0)]);
+        let second = Matrix::from_vec(vec![Cell::new(Point::new(1, 1), 1), Cell::new(Point::new(2, 0), 1)]);
+        let matrix = overlay(&[first, second]);
+
+        assert_eq!(0, matrix.get(0, 0).unwrap().kind);
+        assert_eq!(0, matrix.get(1, 1).unwrap().kind);
+        assert_eq!(1, matrix.get(0, 2).unwrap().kind);
+    }
+}
@@ -0,0 +1,41 @@
+/// Defines how the points of a chart are drawn
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Style {
+    /// Draw one glyph per point
+    Points,
+    /// Connect consecutive points with a line
+    Line,
+    /// Fill the area between the points and the chart's bottom
+    Area,
+    /// Draw a body and wicks for each open/high/low/close bucket
+    Candlestick,
+}
+
+impl Style {
+    pub fn from_str(style: &str) -> Result<Self, ()> {
+        match style {
+            "points" => Ok(Style::Points),
+            "line" => Ok(Style::Line),
+            "area" => Ok(Style::Area),
+            "candlestick" | "candles" => Ok(Style::Candlestick),
+            _ => Err(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_str_test() {
+        assert!(Style::from_str("invalid").is_err());
+        assert!(Style::from_str("").is_err());
+
+        assert_eq!(Style::Points, Style::from_str("points").unwrap());
+        assert_eq!(Style::Line, Style::from_str("line").unwrap());
+        assert_eq!(Style::Area, Style::from_str("area").unwrap());
+        assert_eq!(Style::Candlestick, Style::from_str("candlestick").unwrap());
+        assert_eq!(Style::Candlestick, Style::from_str("candles").unwrap());
+    }
+}
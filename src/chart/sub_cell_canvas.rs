@@ -0,0 +1,226 @@
+use std::collections::BTreeMap;
+use matrix::*;
+use super::configuration::*;
+use super::padding::Padding;
+use super::renderer::Renderer;
+use ui::CoordinatePrecision;
+
+/// Canvas drawing several points per cell (e.g. 2×4 braille dots)
+///
+/// `width` and `height` are given in cells, while the points' coordinates address single dots
+pub struct SubCellCanvas {
+    width: CoordinatePrecision,
+    height: CoordinatePrecision,
+    padding: Padding,
+    renderer: Renderer,
+    x_scala_height: CoordinatePrecision,
+    y_start: Option<CoordinatePrecision>,
+    /// Reference lines by the dot row they are drawn on
+    reference_rows: BTreeMap<CoordinatePrecision, ReferenceLine>,
+}
+
+impl SubCellCanvas {
+    pub fn new(width: CoordinatePrecision, height: CoordinatePrecision, padding: Padding, renderer: Renderer) -> Self {
+        SubCellCanvas { width, height, padding, renderer, x_scala_height: 0, y_start: None, reference_rows: BTreeMap::new() }
+    }
+
+    /// Return a copy of the canvas which draws an x-axis scala of the given height below the points
+    pub fn with_x_scala_height(mut self, x_scala_height: CoordinatePrecision) -> Self {
+        self.x_scala_height = x_scala_height;
+
+        self
+    }
+
+    /// Return a copy of the canvas whose lowest row is fixed instead of following the lowest point
+    pub fn with_y_start(mut self, y_start: Option<CoordinatePrecision>) -> Self {
+        self.y_start = y_start;
+
+        self
+    }
+
+    /// Return a copy of the canvas which draws the reference lines through the cells holding the given dot rows
+    pub fn with_reference_rows(mut self, reference_rows: BTreeMap<CoordinatePrecision, ReferenceLine>) -> Self {
+        self.reference_rows = reference_rows;
+
+        self
+    }
+
+    pub fn draw_points_with_configuration<T: PointTrait>(&self, matrix: Matrix<T>, conf: &dyn Configuration<T>) -> String {
+        if matrix.is_empty() {
+            return "".to_string();
+        }
+        let mut buffer = String::with_capacity((self.width * self.height) as usize);
+        let y_min = matrix.y_min().unwrap();
+        let y_start = self.y_start.unwrap_or_else(|| y_min.saturating_sub(self.padding.bottom));
+
+        let (_, resolution_y) = self.renderer.resolution();
+        for row_number in (0..self.height).rev() {
+            let dot_row_start = y_start as u32 + row_number as u32 * resolution_y as u32;
+            buffer.push_str(&self.draw_label(dot_row_start, &matrix, conf));
+            buffer.push_str(&self.draw_row(dot_row_start, &matrix, conf));
+            buffer.push('\n');
+        }
+        if self.x_scala_height > 0 {
+            buffer.push_str(&conf.draw_x_scala(&self.get_columns(&matrix), self.x_scala_height));
+        }
+        buffer
+    }
+
+    /// Return a point of each cell column (the leftmost and lowest one, if a cell holds several)
+    fn get_columns<T: PointTrait>(&self, matrix: &Matrix<T>) -> Vec<Option<T>> {
+        let (resolution_x, _) = self.renderer.resolution();
+        let x_start = self.get_x_start(matrix) as u32;
+        let mut columns: Vec<Option<T>> = vec![None; self.width as usize];
+        for row in matrix.rows.values() {
+            for (x, point) in row {
+                let x = *x as u32;
+                if x < x_start {
+                    continue;
+                }
+                let column = ((x - x_start) / resolution_x as u32) as usize;
+                if column < columns.len() && columns[column].as_ref().is_none_or(|p| p.x() > point.x()) {
+                    columns[column] = Some(point.clone());
+                }
+            }
+        }
+
+        columns
+    }
+
+    /// Return the leftmost dot column of the canvas
+    fn get_x_start<T: PointTrait>(&self, matrix: &Matrix<T>) -> CoordinatePrecision {
+        matrix.x_min().unwrap().saturating_sub(self.padding.left)
+    }
+
+    /// Label the cell row with the topmost dot row holding points and the number of its lowest dot row
+    fn draw_label<T: PointTrait>(&self, dot_row_start: u32, matrix: &Matrix<T>, conf: &dyn Configuration<T>) -> String {
+        let (_, resolution_y) = self.renderer.resolution();
+        let row_number = dot_row_start.min(CoordinatePrecision::MAX as u32) as CoordinatePrecision;
+        for dot_row in (dot_row_start..dot_row_start + resolution_y as u32).rev() {
+            if dot_row > CoordinatePrecision::MAX as u32 {
+                continue;
+            }
+            if let Some(row) = matrix.get_row(dot_row as CoordinatePrecision) {
+                return conf.draw_row(Some(row), row_number);
+            }
+        }
+
+        conf.draw_row(None, row_number)
+    }
+
+    fn draw_row<T: PointTrait>(&self, dot_row_start: u32, matrix: &Matrix<T>, conf: &dyn Configuration<T>) -> String {
+        let mut buffer = String::with_capacity(self.width as usize);
+        let x_start = self.get_x_start(matrix) as u32;
+        let (resolution_x, resolution_y) = self.renderer.resolution();
+        let (resolution_x, resolution_y) = (resolution_x as u32, resolution_y as u32);
+        let reference_line = self.get_reference_line(dot_row_start);
+
+        for column in 0..self.width as u32 {
+            let mut dots = 0;
+            let mut cell_point: Option<T> = None;
+            for dot_column in 0..resolution_x {
+                for dot_row_from_top in 0..resolution_y {
+                    let x = x_start + column * resolution_x + dot_column;
+                    let y = dot_row_start + resolution_y - 1 - dot_row_from_top;
+                    if x > CoordinatePrecision::MAX as u32 || y > CoordinatePrecision::MAX as u32 {
+                        continue;
+                    }
+                    if let Some(point) = matrix.get(y as CoordinatePrecision, x as CoordinatePrecision) {
+                        dots |= Renderer::dot(dot_column, dot_row_from_top, resolution_y);
+                        cell_point = Some(point);
+                    }
+                }
+            }
+
+            match (cell_point, reference_line) {
+                (Some(point), _) => buffer.push_str(&conf.draw_glyph(Some(point), &self.renderer.glyph(dots))),
+                (None, Some(line)) => buffer.push_str(&line.draw_cell(column as usize, self.width as usize)),
+                (None, None) => buffer.push_str(&conf.draw_point(None)),
+            }
+        }
+
+        buffer
+    }
+
+    /// Return the first reference line drawn through the cell row starting at the given dot row
+    fn get_reference_line(&self, dot_row_start: u32) -> Option<&ReferenceLine> {
+        let (_, resolution_y) = self.renderer.resolution();
+        let start = dot_row_start.min(CoordinatePrecision::MAX as u32) as CoordinatePrecision;
+        let end = (dot_row_start + resolution_y as u32).min(CoordinatePrecision::MAX as u32) as CoordinatePrecision;
+
+        self.reference_rows.range(start..end).next().map(|(_, line)| line)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use point::Point;
+
+    fn draw(canvas: &SubCellCanvas, matrix: Matrix<Point>) -> String {
+        let conf = CallbackConfiguration::new(
+            |_: Option<&Row<Point>>, _: CoordinatePrecision| "".to_string(),
+            |point: Option<Point>| match point {
+                Some(_) => "x".to_string(),
+                None => " ".to_string(),
+            },
+        );
+
+        canvas.draw_points_with_configuration(matrix, &conf)
+    }
+
+    #[test]
+    fn draw_braille_test() {
+        let canvas = SubCellCanvas::new(2, 2, Padding::empty(), Renderer::Braille);
+        let matrix = Matrix::from_vec(vec![
+            Point::new(0, 0),
+            Point::new(1, 1),
+            Point::new(2, 2),
+            Point::new(3, 3),
+            Point::new(3, 7),
+        ]);
+
+        // The upper cell row holds only the dot at (3, 7), the lower one the diagonal
+        assert_eq!(
+            " \u{2808}\n\u{2860}\u{280a}\n",
+            draw(&canvas, matrix)
+        );
+    }
+
+    #[test]
+    fn draw_braille_with_padding_test() {
+        let canvas = SubCellCanvas::new(1, 1, Padding::new(0, 0, 1, 1), Renderer::Braille);
+        let matrix = Matrix::from_vec(vec![Point::new(5, 10)]);
+
+        assert_eq!(
+            "\u{2820}\n",
+            draw(&canvas, matrix)
+        );
+    }
+
+    #[test]
+    fn draw_reference_line_test() {
+        let mut reference_rows = BTreeMap::new();
+        reference_rows.insert(5, ReferenceLine::new(5.0, "-", |text| text.to_string(), ""));
+        let canvas = SubCellCanvas::new(2, 2, Padding::empty(), Renderer::Braille).with_reference_rows(reference_rows);
+
+        // The line runs through the cell row holding its dot row
+        assert_eq!("--\n\u{2840} \n", draw(&canvas, Matrix::from_vec(vec![Point::new(0, 0)])));
+    }
+
+    #[test]
+    fn draw_half_block_test() {
+        let canvas = SubCellCanvas::new(3, 2, Padding::empty(), Renderer::HalfBlock);
+        let matrix = Matrix::from_vec(vec![
+            Point::new(0, 0),
+            Point::new(1, 1),
+            Point::new(2, 2),
+            Point::new(2, 3),
+        ]);
+
+        assert_eq!(
+            "  \u{2588}\n\u{2584}\u{2580} \n",
+            draw(&canvas, matrix)
+        );
+    }
+}
@@ -1,29 +1,129 @@
+use std::collections::BTreeMap;
 use matrix::Matrix;
 use matrix::PointTrait;
-//use super::point::Point;
 use super::Chart;
+use super::cell::Cell;
+use super::series::SeriesCell;
+use super::Mode;
 use ui::CoordinatePrecision;
 
-fn scale_with_factors<T: PointTrait>(matrix: &Matrix<T>, factor_x: f32, factor_y: f32) -> Matrix<T> {
-    matrix.map(|p| p.with_x_y(
-        (p.x() as f32 * factor_x).floor() as CoordinatePrecision,
-        (p.y() as f32 * factor_y).floor() as CoordinatePrecision,
-    ))
+/// Tolerance for rounding errors when values are quantized into rows
+const EPSILON: f64 = 1e-9;
+
+/// Place the points onto the chart's grid according to the chart's mode
+///
+/// Values outside of a fixed y-range are moved onto its bounds. If the mode scales the points down,
+/// columns holding several points are reduced to their extremes (see `downsample()`)
+/// The points' values (see `PointTrait::value()`) are quantized into rows, so the data stays in
+/// float space until it is drawn
+pub fn quantize<T: PointTrait>(chart: &Chart, points: &[T]) -> Matrix<T> {
+    Matrix::from_vec(place(chart, points, get_factor_x_for_mode(chart, points), get_scale_y(chart, points)))
 }
 
-#[allow(unused)]
-fn get_factor<T: PointTrait>(chart: &Chart, matrix: &Matrix<T>, max: Option<f32>) -> (f32, f32) {
-    (get_factor_x(chart, matrix, max), get_factor_y(chart, matrix, max))
+/// Place the points of candles onto the chart's grid
+///
+/// Unlike `quantize()` the columns are never downsampled, as every candle needs its open and close
+/// next to its extremes (see `candlestick::fill()`)
+pub fn quantize_candles<T: PointTrait>(chart: &Chart, points: &[T]) -> Matrix<T> {
+    let factor_x = get_factor_x_for_mode(chart, points);
+    let scale_y = get_scale_y(chart, points);
+
+    Matrix::from_vec(points.iter().map(|p| place_point(chart, p, factor_x, scale_y)).collect())
+}
+
+/// Place each of the series onto the chart's grid
+///
+/// All series share the same scale, so they can be overlaid (see `series::overlay()`)
+pub fn quantize_series<T: PointTrait>(chart: &Chart, series: &[Vec<T>]) -> Vec<Matrix<SeriesCell<T>>> {
+    let all_points: Vec<T> = series.concat();
+    let factor_x = get_factor_x_for_mode(chart, &all_points);
+    let scale_y = get_scale_y(chart, &all_points);
+
+    series.iter().enumerate()
+        .map(|(index, points)| {
+            Matrix::from_vec(place(chart, points, factor_x, scale_y).into_iter().map(|p| Cell::new(p, index)).collect())
+        })
+        .collect()
+}
+
+/// Move the points into the cell columns in which the chart draws them
+///
+/// The columns are counted from the leftmost one of the chart's canvas, so a sub-chart drawn from the
+/// result lines up with the chart. The rows are left as they are
+pub fn place_columns<T: PointTrait>(chart: &Chart, points: &[T]) -> Vec<T> {
+    let factor_x = get_factor_x_for_mode(chart, points);
+    let column = |p: &T| (p.x() as f32 * factor_x).floor() as CoordinatePrecision;
+    // The canvas leaves one column of padding to the left of the points
+    let x_start = match points.iter().map(column).min() {
+        Some(x_min) => x_min.saturating_sub(1),
+        None => return vec![],
+    };
+    let resolution_x = chart.resolution().0;
+
+    points.iter().map(|p| p.with_x((column(p) - x_start) / resolution_x)).collect()
 }
 
-fn get_factor_x<T: PointTrait>(chart: &Chart, matrix: &Matrix<T>, max: Option<f32>) -> f32 {
-    if matrix.is_empty() {
-        return 1.0;
+fn place<T: PointTrait>(chart: &Chart, points: &[T], factor_x: f32, scale_y: ScaleY) -> Vec<T> {
+    let column = |p: &T| (p.x() as f32 * factor_x).floor() as CoordinatePrecision;
+    // Only scaling down makes several points collide in one column
+    let points = match chart.mode {
+        Mode::ScaleDown | Mode::ScaleDownX | Mode::Log => downsample(points, &column),
+        _ => points.to_vec(),
+    };
+
+    points.iter().map(|p| place_point(chart, p, factor_x, scale_y)).collect()
+}
+
+fn place_point<T: PointTrait>(chart: &Chart, point: &T, factor_x: f32, scale_y: ScaleY) -> T {
+    let column = (point.x() as f32 * factor_x).floor() as CoordinatePrecision;
+
+    point.with_x_y(column, scale_y.apply(chart.y_range.clamp(point.value())))
+}
+
+fn get_factor_x_for_mode<T: PointTrait>(chart: &Chart, points: &[T]) -> f32 {
+    match chart.mode {
+        Mode::Truncate | Mode::ScaleY | Mode::ScaleDownY => 1.0,
+        Mode::Scale | Mode::ScaleX => get_factor_x(chart, points, None),
+        Mode::ScaleDown | Mode::ScaleDownX | Mode::Log => get_factor_x(chart, points, Some(1.0)),
     }
+}
+
+/// Keep only the points with the lowest and the highest value of each column
+///
+/// If the points are scaled down, several of them fall into the same column. Reducing them to their
+/// extremes keeps spikes visible no matter how long the history is. The order of the points is kept
+fn downsample<T: PointTrait, F>(points: &[T], column: &F) -> Vec<T>
+    where F: Fn(&T) -> CoordinatePrecision {
+    // Index of the lowest and the highest point of each column
+    let mut extremes: BTreeMap<CoordinatePrecision, (usize, usize)> = BTreeMap::new();
+    for (index, point) in points.iter().enumerate() {
+        let (min, max) = extremes.entry(column(point)).or_insert((index, index));
+        if point.value() < points[*min].value() {
+            *min = index;
+        }
+        if point.value() > points[*max].value() {
+            *max = index;
+        }
+    }
+
+    let mut keep: Vec<usize> = extremes.values().flat_map(|&(min, max)| vec![min, max]).collect();
+    keep.sort_unstable();
+    keep.dedup();
+
+    keep.into_iter().map(|index| points[index].clone()).collect()
+}
+
+fn get_factor_x<T: PointTrait>(chart: &Chart, points: &[T], max: Option<f32>) -> f32 {
+    let x_min = points.iter().map(|p| p.x()).min();
+    let x_max = points.iter().map(|p| p.x()).max();
+    let (x_min, x_max) = match (x_min, x_max) {
+        (Some(x_min), Some(x_max)) => (x_min, x_max),
+        _ => return 1.0,
+    };
 
     // The scala for the y-axis takes away from the available width
-    let result = (chart.width() - chart.y_scala_width) as f32 /
-        (1 + matrix.x_max().unwrap() - matrix.x_min().unwrap()) as f32;
+    let result = ((chart.width() - chart.y_scala_width) * chart.resolution().0) as f32 /
+        (1 + x_max - x_min) as f32;
     //   ^__ include the max point
 
     match max {
@@ -36,48 +136,88 @@ fn get_factor_x<T: PointTrait>(chart: &Chart, matrix: &Matrix<T>, max: Option<f3
     }
 }
 
-fn get_factor_y<T: PointTrait>(chart: &Chart, matrix: &Matrix<T>, max: Option<f32>) -> f32 {
-    if matrix.is_empty() {
-        return 1.0;
-    }
-
-    // The scala for the x-axis takes away from the available height
-    let result = (chart.height() - chart.x_scala_height) as f32 /
-        (1 + matrix.y_max().unwrap() - matrix.y_min().unwrap()) as f32;
-    //   ^__ include the max point
+/// Return the factor which fits the range of values into the available rows
+fn get_factor_y(chart: &Chart, range: f64, max: Option<f64>) -> f64 {
+    // The scala for the x-axis and the sub-chart take away from the available height
+    let available = f64::from(chart.points_height() * chart.resolution().1);
+    let result = if range > 0.0 && available > 1.0 {
+        // The minimum lands on the first and the maximum on the last row
+        (available - 1.0) / range
+    } else {
+        1.0
+    };
 
     match max {
         None => result,
-        Some(max) => if result < max {
-            result
-        } else {
-            max
-        }
+        Some(max) => result.min(max),
     }
 }
 
-pub fn scale<T: PointTrait>(chart: &Chart, matrix: &Matrix<T>) -> Matrix<T> {
-    scale_with_factors(matrix, get_factor_x(chart, matrix, None), get_factor_y(chart, matrix, None))
+/// Mapping of the points' values onto the rows of the chart
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ScaleY {
+    /// The distance to `origin` is multiplied with the factor
+    Linear { factor: f64, origin: f64 },
+    /// Equal ratios between the values take up equal space (`origin` is the logarithm of the value at row 0)
+    ///
+    /// The offset is added to the values before taking the logarithm, so values relative to an anchor
+    /// (which drop below zero) are scaled by their ratio to the anchor (see `YAxis::log_offset()`)
+    Logarithmic { factor: f64, origin: f64, offset: f64 },
 }
 
-pub fn scale_x<T: PointTrait>(chart: &Chart, matrix: &Matrix<T>) -> Matrix<T> {
-    scale_with_factors(matrix, get_factor_x(chart, matrix, None), 1.0)
-}
+impl ScaleY {
+    /// Return the row for the value
+    pub fn apply(self, value: f64) -> CoordinatePrecision {
+        let row = match self {
+            ScaleY::Linear { factor, origin } => (value - origin) * factor,
+            ScaleY::Logarithmic { factor, origin, offset } => (ln(value + offset) - origin) * factor,
+        };
 
-pub fn scale_y<T: PointTrait>(chart: &Chart, matrix: &Matrix<T>) -> Matrix<T> {
-    scale_with_factors(matrix, 1.0, get_factor_y(chart, matrix, None))
-}
+        (row + EPSILON).floor().max(0.0).min(f64::from(CoordinatePrecision::MAX)) as CoordinatePrecision
+    }
 
-pub fn scale_down<T: PointTrait>(chart: &Chart, matrix: &Matrix<T>) -> Matrix<T> {
-    scale_with_factors(matrix, get_factor_x(chart, matrix, Some(1.0)), get_factor_y(chart, matrix, Some(1.0)))
+    /// Return the value at which the given (fractional) row starts
+    pub fn invert(self, row: f64) -> f64 {
+        // Match the tolerance of `apply()`
+        let row = row - EPSILON;
+        match self {
+            ScaleY::Linear { factor, origin } => row / factor + origin,
+            ScaleY::Logarithmic { factor, origin, offset } => (row / factor + origin).exp() - offset,
+        }
+    }
 }
 
-pub fn scale_down_x<T: PointTrait>(chart: &Chart, matrix: &Matrix<T>) -> Matrix<T> {
-    scale_with_factors(matrix, get_factor_x(chart, matrix, Some(1.0)), 1.0)
-}
+/// Return the mapping of the points' values in the chart's mode
+pub fn get_scale_y<T: PointTrait>(chart: &Chart, points: &[T]) -> ScaleY {
+    let v_min = points.iter().map(|p| p.value()).fold(None, |min: Option<f64>, v| Some(min.map_or(v, |min| min.min(v))));
+    let v_max = points.iter().map(|p| p.value()).fold(None, |max: Option<f64>, v| Some(max.map_or(v, |max| max.max(v))));
+    let (v_min, v_max) = match (v_min, v_max) {
+        (Some(v_min), Some(v_max)) => (v_min, v_max),
+        _ => return ScaleY::Linear { factor: 1.0, origin: 0.0 },
+    };
+    let offset = chart.y_axis.log_offset();
+    let logarithmic = |v_min: f64, v_max: f64| {
+        let (ln_min, ln_max) = (ln(v_min + offset), ln(v_max + offset));
+        ScaleY::Logarithmic { factor: get_factor_y(chart, ln_max - ln_min, None), origin: ln_min, offset }
+    };
+    if !chart.y_range.is_auto() {
+        // A fixed range always fills the available height
+        let (v_min, v_max) = chart.y_range.resolve(v_min, v_max);
+        return match chart.mode {
+            Mode::Log => logarithmic(v_min, v_max),
+            _ => ScaleY::Linear { factor: get_factor_y(chart, v_max - v_min, None), origin: v_min },
+        };
+    }
 
-pub fn scale_down_y<T: PointTrait>(chart: &Chart, matrix: &Matrix<T>) -> Matrix<T> {
-    scale_with_factors(matrix, 1.0, get_factor_y(chart, matrix, Some(1.0)))
+    match chart.mode {
+        Mode::Truncate | Mode::ScaleX | Mode::ScaleDownX => ScaleY::Linear { factor: 1.0, origin: v_min },
+        Mode::Scale | Mode::ScaleY => ScaleY::Linear { factor: get_factor_y(chart, v_max - v_min, None), origin: v_min },
+        Mode::ScaleDown | Mode::ScaleDownY => ScaleY::Linear { factor: get_factor_y(chart, v_max - v_min, Some(1.0)), origin: v_min },
+        Mode::Log => logarithmic(v_min, v_max),
+    }
 }
 
-
+/// Return the natural logarithm of the value (values below the smallest positive number are clamped)
+fn ln(value: f64) -> f64 {
+    value.max(f64::MIN_POSITIVE).ln()
+}
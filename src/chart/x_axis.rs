@@ -0,0 +1,40 @@
+use util;
+
+/// Defines how the points are placed along the x-axis
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum XAxis {
+    /// Place the points next to each other, using their position in the series
+    Index,
+    /// Place the points according to their time, each column covering the given number of seconds
+    Time(i64),
+}
+
+impl XAxis {
+    /// Build the x-axis from "index" or a column duration like "30s", "5m" or "1h"
+    pub fn from_str(input: &str) -> Result<Self, ()> {
+        match input {
+            "index" => Ok(XAxis::Index),
+            _ => match util::parse_duration(input) {
+                Some(seconds) => Ok(XAxis::Time(seconds)),
+                None => Err(()),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_str_test() {
+        assert!(XAxis::from_str("invalid").is_err());
+        assert!(XAxis::from_str("").is_err());
+        assert!(XAxis::from_str("0s").is_err());
+
+        assert_eq!(XAxis::Index, XAxis::from_str("index").unwrap());
+        assert_eq!(XAxis::Time(30), XAxis::from_str("30").unwrap());
+        assert_eq!(XAxis::Time(300), XAxis::from_str("5m").unwrap());
+        assert_eq!(XAxis::Time(3_600), XAxis::from_str("1h").unwrap());
+    }
+}
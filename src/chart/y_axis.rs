@@ -0,0 +1,47 @@
+use rate::RELATIVE_SCALE;
+
+/// Defines what the y-axis shows
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum YAxis {
+    /// The price in the quote currency
+    Price,
+    /// The change in percent from the anchor price, or from the first sample if none is given
+    PercentChange(Option<f64>),
+}
+
+impl YAxis {
+    /// Build the percent change y-axis relative to the given anchor price (e.g. "19500.5")
+    pub fn from_anchor(input: &str) -> Result<Self, ()> {
+        match input.parse::<f64>() {
+            Ok(anchor) if anchor > 0.0 && anchor.is_finite() => Ok(YAxis::PercentChange(Some(anchor))),
+            _ => Err(()),
+        }
+    }
+
+    /// Return the offset added to the values before a logarithmic scale is applied
+    ///
+    /// Percent changes are centered on zero (see `Rate::price_to_value_relative()`), so shifting them by
+    /// one unit of the ratio scales them by their ratio to the anchor, which is always positive
+    pub fn log_offset(&self) -> f64 {
+        match *self {
+            YAxis::Price => 0.0,
+            YAxis::PercentChange(_) => RELATIVE_SCALE,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_anchor_test() {
+        assert!(YAxis::from_anchor("invalid").is_err());
+        assert!(YAxis::from_anchor("").is_err());
+        assert!(YAxis::from_anchor("0").is_err());
+        assert!(YAxis::from_anchor("-1.5").is_err());
+
+        assert_eq!(YAxis::PercentChange(Some(19500.5)), YAxis::from_anchor("19500.5").unwrap());
+        assert_eq!(YAxis::PercentChange(Some(3.0)), YAxis::from_anchor("3").unwrap());
+    }
+}
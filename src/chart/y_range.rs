@@ -0,0 +1,143 @@
+/// Marker drawn instead of points above the range
+pub const MARKER_ABOVE: &str = "\u{25b2}";
+/// Marker drawn instead of points below the range
+pub const MARKER_BELOW: &str = "\u{25bc}";
+
+/// Range of values shown on the y-axis
+///
+/// Missing bounds follow the data, so the default range fits the axis to the points
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct YRange {
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+}
+
+impl YRange {
+    /// Build a range following the data
+    pub fn auto() -> Self {
+        YRange { min: None, max: None }
+    }
+
+    /// Build a range with the given bounds
+    pub fn new(min: Option<f64>, max: Option<f64>) -> Result<Self, ()> {
+        let is_valid = |bound: Option<f64>| bound.is_none_or(f64::is_finite);
+        if !is_valid(min) || !is_valid(max) {
+            return Err(());
+        }
+        match (min, max) {
+            (Some(min), Some(max)) if min >= max => Err(()),
+            _ => Ok(YRange { min, max }),
+        }
+    }
+
+    /// Return if none of the bounds are fixed
+    pub fn is_auto(&self) -> bool {
+        self.min.is_none() && self.max.is_none()
+    }
+
+    /// Return the marker for a value outside of the bounds
+    pub fn marker(&self, value: f64) -> Option<&'static str> {
+        if self.min.is_some_and(|min| value < min) {
+            Some(MARKER_BELOW)
+        } else if self.max.is_some_and(|max| value > max) {
+            Some(MARKER_ABOVE)
+        } else {
+            None
+        }
+    }
+
+    /// Move the value into the bounds
+    pub fn clamp(&self, value: f64) -> f64 {
+        let value = self.min.map_or(value, |min| value.max(min));
+
+        self.max.map_or(value, |max| value.min(max))
+    }
+
+    /// Return the bounds, with the missing ones taken from the data's `min` and `max`
+    pub fn resolve(&self, min: f64, max: f64) -> (f64, f64) {
+        match (self.min, self.max) {
+            (Some(range_min), Some(range_max)) => (range_min, range_max),
+            (Some(range_min), None) => (range_min, max.max(range_min)),
+            (None, Some(range_max)) => (min.min(range_max), range_max),
+            (None, None) => (min, max),
+        }
+    }
+
+    /// Return a fixed range scaled by `factor` around the center of the resolved bounds
+    ///
+    /// A factor below one zooms in, a factor above one zooms out. Bounds without a span to scale are kept as they are
+    pub fn zoom(&self, min: f64, max: f64, factor: f64) -> Self {
+        let (min, max) = self.resolve(min, max);
+        let center = (min + max) / 2.0;
+        let half_span = (max - min) / 2.0 * factor;
+        if half_span > 0.0 && half_span.is_finite() {
+            YRange { min: Some(center - half_span), max: Some(center + half_span) }
+        } else {
+            *self
+        }
+    }
+
+    /// Return the range with both bounds converted by the callback
+    pub fn map<F>(&self, callback: F) -> Self
+        where F: Fn(f64) -> f64 {
+        YRange { min: self.min.map(&callback), max: self.max.map(&callback) }
+    }
+}
+
+impl Default for YRange {
+    fn default() -> Self {
+        YRange::auto()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_test() {
+        assert!(YRange::new(Some(2.0), Some(1.0)).is_err());
+        assert!(YRange::new(Some(1.0), Some(1.0)).is_err());
+        assert!(YRange::new(Some(f64::NAN), None).is_err());
+        assert!(YRange::new(None, Some(f64::INFINITY)).is_err());
+
+        assert!(YRange::new(None, None).unwrap().is_auto());
+        assert!(!YRange::new(Some(1.0), None).unwrap().is_auto());
+        assert_eq!(YRange { min: Some(1.0), max: Some(2.0) }, YRange::new(Some(1.0), Some(2.0)).unwrap());
+    }
+
+    #[test]
+    fn marker_and_clamp_test() {
+        let range = YRange::new(Some(10.0), Some(20.0)).unwrap();
+
+        assert_eq!(None, range.marker(10.0));
+        assert_eq!(None, range.marker(20.0));
+        assert_eq!(10.0, range.clamp(5.0));
+        assert_eq!(15.0, range.clamp(15.0));
+        assert_eq!(20.0, range.clamp(25.0));
+        assert_eq!(Some(MARKER_BELOW), range.marker(5.0));
+        assert_eq!(None, range.marker(15.0));
+        assert_eq!(Some(MARKER_ABOVE), range.marker(25.0));
+
+        let range = YRange::new(None, Some(20.0)).unwrap();
+        assert_eq!(None, range.marker(-1_000.0));
+        assert_eq!(-1_000.0, range.clamp(-1_000.0));
+    }
+
+    #[test]
+    fn resolve_test() {
+        assert_eq!((1.0, 5.0), YRange::auto().resolve(1.0, 5.0));
+        assert_eq!((0.0, 10.0), YRange::new(Some(0.0), Some(10.0)).unwrap().resolve(1.0, 5.0));
+        assert_eq!((2.0, 5.0), YRange::new(Some(2.0), None).unwrap().resolve(1.0, 5.0));
+        assert_eq!((8.0, 8.0), YRange::new(Some(8.0), None).unwrap().resolve(1.0, 5.0));
+        assert_eq!((1.0, 3.0), YRange::new(None, Some(3.0)).unwrap().resolve(1.0, 5.0));
+    }
+
+    #[test]
+    fn zoom_test() {
+        assert_eq!(YRange { min: Some(2.0), max: Some(4.0) }, YRange::auto().zoom(1.0, 5.0, 0.5));
+        assert_eq!(YRange { min: Some(-2.0), max: Some(8.0) }, YRange::new(Some(1.0), Some(5.0)).unwrap().zoom(0.0, 0.0, 2.5));
+        assert_eq!(YRange::auto(), YRange::auto().zoom(3.0, 3.0, 0.5));
+        assert_eq!(YRange { min: Some(8.0), max: None }, YRange::new(Some(8.0), None).unwrap().zoom(1.0, 5.0, 0.5));
+    }
+}
@@ -36,6 +36,97 @@ fn get_mode(matches: &ArgMatches) -> chart::Mode {
     }
 }
 
+fn get_style(matches: &ArgMatches) -> chart::Style {
+    match matches.value_of("style") {
+        Some(style_arg) => {
+            match chart::Style::from_str(style_arg) {
+                Ok(style) => style,
+                Err(_) => error!("Invalid style '{}' given", style_arg),
+            }
+        }
+        None => chart::Style::Points,
+    }
+}
+
+fn get_renderer(matches: &ArgMatches) -> chart::Renderer {
+    match matches.value_of("renderer") {
+        Some(renderer_arg) => {
+            match chart::Renderer::from_str(renderer_arg) {
+                // Only points are drawn with sub-cell dots (see `Chart::resolution()`)
+                Ok(renderer) if renderer != chart::Renderer::Cell && get_style(matches) != chart::Style::Points => {
+                    error!("Argument 'renderer' only supports the points style")
+                }
+                Ok(renderer) => renderer,
+                Err(_) => error!("Invalid renderer '{}' given", renderer_arg),
+            }
+        }
+        None => chart::Renderer::Cell,
+    }
+}
+
+fn get_x_axis(matches: &ArgMatches) -> chart::XAxis {
+    match matches.value_of("x-axis") {
+        Some(x_axis_arg) => {
+            match chart::XAxis::from_str(x_axis_arg) {
+                Ok(x_axis) => x_axis,
+                Err(_) => error!("Invalid x-axis '{}' given", x_axis_arg),
+            }
+        }
+        None => chart::XAxis::Index,
+    }
+}
+
+fn get_y_axis(matches: &ArgMatches) -> chart::YAxis {
+    match matches.value_of("anchor") {
+        Some(anchor_arg) => {
+            match chart::YAxis::from_anchor(anchor_arg) {
+                Ok(y_axis) => y_axis,
+                Err(_) => error!("Invalid anchor price '{}' given", anchor_arg),
+            }
+        }
+        None if matches.is_present("percent") => chart::YAxis::PercentChange(None),
+        None => chart::YAxis::Price,
+    }
+}
+
+fn get_y_bound(name: &str, matches: &ArgMatches) -> Option<f64> {
+    match matches.value_of(name) {
+        Some(arg) => {
+            match arg.parse::<f64>() {
+                Ok(bound) => Some(bound),
+                Err(_) => error!("Argument '{}' must be a valid number", name),
+            }
+        }
+        None => None,
+    }
+}
+
+fn get_y_range(matches: &ArgMatches) -> chart::YRange {
+    match chart::YRange::new(get_y_bound("y-min", matches), get_y_bound("y-max", matches)) {
+        Ok(y_range) => y_range,
+        Err(_) => error!("Argument 'y-min' must be smaller than 'y-max'"),
+    }
+}
+
+/// Return the candle width in seconds, if candles should be drawn
+///
+/// The candlestick style falls back to one candle per x-axis column, or one per minute
+fn get_candle_width(matches: &ArgMatches, chart: &chart::Chart) -> Option<i64> {
+    match matches.value_of("candles") {
+        Some(candles_arg) => {
+            match util::parse_duration(candles_arg) {
+                Some(seconds) => Some(seconds),
+                None => error!("Invalid candle width '{}' given", candles_arg),
+            }
+        }
+        None => match (chart.style, chart.x_axis) {
+            (chart::Style::Candlestick, chart::XAxis::Time(seconds_per_column)) => Some(seconds_per_column),
+            (chart::Style::Candlestick, chart::XAxis::Index) => Some(60),
+            _ => None,
+        },
+    }
+}
+
 fn get_dimension_argument(name: &str, matches: &ArgMatches) -> CoordinatePrecision {
     let default = 0;
     match matches.value_of(name) {
@@ -74,6 +165,14 @@ fn get_history_size(matches: &ArgMatches) -> Option<usize> {
     }
 }
 
+/// Return the history size needed to fill the chart if each column covers `seconds_per_column`
+fn get_time_axis_history_size(chart: &chart::Chart, seconds_per_column: i64, interval: u64) -> usize {
+    let columns = chart.point_columns();
+    let samples_per_column = (seconds_per_column as u64 * 1_000 / interval.max(1)).max(1) as usize;
+
+    columns * samples_per_column
+}
+
 fn get_interval(matches: &ArgMatches) -> u64 {
     let default: u64 = 1_000;
     match matches.value_of("interval") {
@@ -114,20 +213,254 @@ fn get_provider(matches: &ArgMatches) -> String {
     }
 }
 
-fn get_value(matches: &ArgMatches) -> Option<f32> {
+fn get_quote(matches: &ArgMatches) -> String {
+    match matches.value_of("quote") {
+        Some(quote) => quote.to_uppercase(),
+        None => rate::DEFAULT_QUOTE.to_string(),
+    }
+}
+
+fn get_value(matches: &ArgMatches) -> Option<rate::Price> {
     match matches.value_of("VALUE") {
-        Some(arg) => arg.parse::<f32>().ok(),
+        Some(arg) => arg.parse::<rate::Price>().ok(),
         None => None,
     }
 }
 
-fn get_currency(matches: &ArgMatches) -> rate::Currency {
-    let input = matches.value_of("CURRENCY").unwrap();
-    if let Some(c) = rate::Currency::new(input) {
-        return c;
+fn get_currency_registry(matches: &ArgMatches) -> rate::CurrencyRegistry {
+    let mut registry = rate::CurrencyRegistry::with_defaults();
+    if let Some(path) = matches.value_of("currencies") {
+        if let Err(e) = registry.load_file(path) {
+            error!("{}", e)
+        }
+    }
+
+    registry
+}
+
+fn get_currency(matches: &ArgMatches, registry: &mut rate::CurrencyRegistry, provider: &str, quote: &str) -> rate::Currency {
+    find_currency(matches.value_of("CURRENCY").unwrap(), registry, provider, quote)
+}
+
+fn get_compared_currencies(matches: &ArgMatches, registry: &mut rate::CurrencyRegistry, provider: &str, quote: &str) -> Vec<rate::Currency> {
+    match matches.value_of("compare") {
+        Some(arg) => {
+            check_series_style(matches, "compare");
+            arg.split(',')
+                .map(str::trim)
+                .filter(|input| !input.is_empty())
+                .map(|input| find_currency(input, registry, provider, quote))
+                .collect()
+        }
+        None => vec![],
+    }
+}
+
+fn find_currency(input: &str, registry: &mut rate::CurrencyRegistry, provider: &str, quote: &str) -> rate::Currency {
+    if let Some(c) = registry.get(input) {
+        return c.clone();
+    }
+
+    // Look the input up in the provider's listing, which is only fetched once (even if that fails)
+    if !registry.has_listing(provider) {
+        let rates = rate_provider::get_all(provider, quote).unwrap_or_default();
+        registry.register_listing(provider, &rates);
+        if let Some(c) = registry.get(input) {
+            return c.clone();
+        }
+    }
+
+    // Let the provider decide if it knows the symbol
+    rate::Currency::from_symbol(input)
+}
+
+/// Return the settings of each pane of the dashboard with its currency
+fn get_panes(matches: &ArgMatches, registry: &mut rate::CurrencyRegistry, provider: &str, quote: &str) -> Vec<(rate_printer::PaneSpec, rate::Currency)> {
+    match matches.values_of("pane") {
+        Some(values) => values
+            .map(|pane_arg| match rate_printer::PaneSpec::from_str(pane_arg) {
+                Ok(pane_spec) => {
+                    let currency = find_currency(&pane_spec.currency, registry, get_pane_provider(&pane_spec, provider), quote);
+                    (pane_spec, currency)
+                }
+                Err(_) => error!("Invalid pane '{}' given", pane_arg),
+            })
+            .collect(),
+        None => vec![],
+    }
+}
+
+/// Return the provider of the pane, falling back to the global one
+fn get_pane_provider<'a>(pane_spec: &'a rate_printer::PaneSpec, provider: &'a str) -> &'a str {
+    pane_spec.provider.as_ref().map_or(provider, |provider| provider.as_str())
+}
+
+fn get_references(matches: &ArgMatches) -> Vec<rate_printer::Reference> {
+    match matches.values_of("reference") {
+        Some(values) => values
+            .map(|reference_arg| match rate_printer::Reference::from_str(reference_arg) {
+                Ok(reference) => reference,
+                Err(_) => error!("Invalid reference '{}' given", reference_arg),
+            })
+            .collect(),
+        None => vec![],
+    }
+}
+
+fn get_indicators(matches: &ArgMatches) -> Vec<rate::Indicator> {
+    match matches.values_of("indicator") {
+        Some(values) => {
+            check_series_style(matches, "indicator");
+            values
+                .map(|indicator_arg| match rate::Indicator::from_str(indicator_arg) {
+                    Ok(indicator) => indicator,
+                    Err(_) => error!("Invalid indicator '{}' given", indicator_arg),
+                })
+                .collect()
+        }
+        None => vec![],
+    }
+}
+
+/// Exit if the chart can not be drawn as series of rates, as the argument requires
+///
+/// Series are drawn from the raw rates as points or lines (see `RatePrinter::draw_series_chart()`)
+fn check_series_style(matches: &ArgMatches, argument: &str) {
+    if matches.is_present("candles") {
+        error!("Argument '{}' can not be combined with 'candles'", argument)
+    }
+    match get_style(matches) {
+        chart::Style::Points | chart::Style::Line => {}
+        _ => error!("Argument '{}' only supports the points and line styles", argument),
+    }
+}
+
+fn get_oscillator(matches: &ArgMatches) -> Option<rate::Oscillator> {
+    let oscillator_arg = matches.value_of("oscillator")?;
+    match rate::Oscillator::from_str(oscillator_arg) {
+        Ok(oscillator) => Some(oscillator),
+        Err(_) => error!("Invalid oscillator '{}' given", oscillator_arg),
+    }
+}
+
+/// Return the number of panes per row of the dashboard (by default the panes are arranged in a square)
+fn get_dashboard_columns(matches: &ArgMatches, pane_count: usize) -> usize {
+    match get_dimension_argument("columns", matches) {
+        0 => (pane_count as f64).sqrt().ceil() as usize,
+        columns => columns as usize,
+    }
+}
+
+fn build_chart(matches: &ArgMatches, width: CoordinatePrecision, height: CoordinatePrecision, mode: chart::Mode) -> chart::Chart {
+    let mut chart = chart::Chart::new(width, height, 2, 10, mode);
+    chart.x_axis = get_x_axis(matches);
+    chart.y_axis = get_y_axis(matches);
+    chart.style = get_style(matches);
+    chart.renderer = get_renderer(matches);
+
+    chart
+}
+
+fn build_printer<'a>(
+    matches: &'a ArgMatches,
+    chart: chart::Chart,
+    value: Option<rate::Price>,
+    provider: &'a str,
+    quote: &'a str,
+    fill: &'a str,
+    space: &'a str,
+) -> rate_printer::RatePrinter<'a> {
+    let candle_width = get_candle_width(matches, &chart);
+    let history_size = match (get_history_size(matches), chart.x_axis) {
+        (None, chart::XAxis::Time(seconds_per_column)) => {
+            Some(get_time_axis_history_size(&chart, seconds_per_column, get_interval(matches)))
+        }
+        (history_size, _) => history_size,
+    };
+
+    let mut printer = match rate_printer::RatePrinter::new(chart, value, provider, quote, fill, space, history_size) {
+        Ok(p) => p,
+        Err(error) => error!("{}", error),
+    };
+    if let Some(area_fill) = matches.value_of("area-fill") {
+        printer.set_area_fill(area_fill);
+    }
+    if let Some(candle_width) = candle_width {
+        printer.enable_candles(candle_width);
+    }
+    if matches.is_present("volume") {
+        printer.enable_volume();
+    }
+    for reference in get_references(matches) {
+        printer.add_reference(reference);
     }
+    for indicator in get_indicators(matches) {
+        printer.add_indicator(indicator);
+    }
+    if let Some(oscillator) = get_oscillator(matches) {
+        printer.set_oscillator(oscillator);
+    }
+    printer.set_y_range(get_y_range(matches));
 
-    panic!("Currency {} not supported", input)
+    printer
+}
+
+/// Build a dashboard with a pane for each of the specs, arranged in a grid filling the screen
+fn build_dashboard<'a>(
+    matches: &'a ArgMatches,
+    panes: &'a [(rate_printer::PaneSpec, rate::Currency)],
+    value: Option<rate::Price>,
+    provider: &'a str,
+    quote: &'a str,
+    fill: &'a str,
+    space: &'a str,
+) -> rate_printer::Dashboard<'a> {
+    let screen = match ui::Screen::default() {
+        Ok(screen) => screen,
+        Err(error) => error!("{}", error),
+    };
+    let columns = get_dashboard_columns(matches, panes.len());
+    let areas = match ui::layout::grid(rate_printer::Dashboard::get_area(screen.size()), panes.len(), columns) {
+        Ok(areas) => areas,
+        Err(error) => error!("{}", error),
+    };
+
+    let mut dashboard = rate_printer::Dashboard::new(screen, columns);
+    for ((pane_spec, currency), pane) in panes.iter().zip(areas) {
+        let pane = pane.shrink(pane_spec.width, pane_spec.height);
+        if !rate_printer::Dashboard::fits(&pane) {
+            error!("The pane for '{}' is too small ({}x{})", pane_spec.currency, pane.size.width, pane.size.height)
+        }
+        let provider = get_pane_provider(pane_spec, provider);
+        let mode = pane_spec.mode.unwrap_or_else(|| get_mode(matches));
+        let chart = build_chart(matches, pane.size.width, pane.size.height - 1, mode);
+
+        dashboard.add_pane(pane, pane_spec, build_printer(matches, chart, value, provider, quote, fill, space), currency.clone());
+    }
+
+    dashboard
+}
+
+/// The full-screen view of a single currency or the dashboard
+enum View<'a> {
+    Single(Box<rate_printer::RatePrinter<'a>>, rate::Currency),
+    Dashboard(rate_printer::Dashboard<'a>),
+}
+
+impl<'a> View<'a> {
+    fn get_and_print_rates(&mut self) -> Result<(), ui::Error> {
+        match *self {
+            View::Single(ref mut printer, ref currency) => printer.get_and_print_rates(currency).map(|_| ()),
+            View::Dashboard(ref mut dashboard) => dashboard.get_and_print_rates(),
+        }
+    }
+
+    fn printers_mut(&mut self) -> Vec<&mut rate_printer::RatePrinter<'a>> {
+        match *self {
+            View::Single(ref mut printer, _) => vec![&mut **printer],
+            View::Dashboard(ref mut dashboard) => dashboard.printers_mut(),
+        }
+    }
 }
 
 fn get_all_providers() -> String {
@@ -136,6 +469,29 @@ fn get_all_providers() -> String {
         .join(", ")
 }
 
+/// Action triggered by a hotkey
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum KeyAction {
+    Quit,
+    /// Keep the y-axis at the range currently visible
+    FixYRange,
+    /// Fit the y-axis to the data again
+    ResetYRange,
+    ZoomIn,
+    ZoomOut,
+    None,
+}
+
+fn get_key_action(key: char) -> KeyAction {
+    match key {
+        'q' => KeyAction::Quit,
+        'f' => KeyAction::FixYRange,
+        'a' => KeyAction::ResetYRange,
+        '+' => KeyAction::ZoomIn,
+        '-' => KeyAction::ZoomOut,
+        _ => KeyAction::None,
+    }
+}
 
 fn exit() {
     term_style::cursor::show_cursor();
@@ -153,7 +509,7 @@ fn main() {
         .about("Watch crypto-currency prices")
         .arg(Arg::with_name("CURRENCY")
             .help("Sets the currency to monitor")
-            .required(true)
+            .required_unless("pane")
             .index(1))
         .arg(Arg::with_name("VALUE")
             .help("Coin value to convert")
@@ -161,7 +517,56 @@ fn main() {
         .arg(Arg::with_name("mode")
             .long("mode")
             .short("m")
-            .help("Sets the chart's display mode")
+            .help("Sets the chart's display mode [truncate, scale, scale-x, scale-y, scale-down, scale-down-x, scale-down-y, log]")
+            .takes_value(true))
+        .arg(Arg::with_name("style")
+            .long("style")
+            .help("Sets how the chart is drawn [points, line, area, candlestick]")
+            .takes_value(true))
+        .arg(Arg::with_name("renderer")
+            .long("renderer")
+            .help("Sets how points are mapped onto the terminal cells [cell, half-block, braille] (sub-cell renderers support the points style only)")
+            .takes_value(true))
+        .arg(Arg::with_name("x-axis")
+            .long("x-axis")
+            .help("Place points by their index ('index') or by their time, with the given duration per column (e.g. '30s', '5m', '1h')")
+            .takes_value(true))
+        .arg(Arg::with_name("percent")
+            .long("percent")
+            .help("Plot the change in percent from the first rate in the history instead of the price"))
+        .arg(Arg::with_name("anchor")
+            .long("anchor")
+            .help("Plot the change in percent from the given price instead of the price")
+            .takes_value(true))
+        .arg(Arg::with_name("compare")
+            .long("compare")
+            .help("Overlay the given currencies (comma separated, e.g. 'eth,ltc'), each plotted as the change in percent from its first rate (points and line styles only)")
+            .takes_value(true)
+            .conflicts_with("anchor"))
+        .arg(Arg::with_name("pane")
+            .long("pane")
+            .help("Show a dashboard with a pane for each of the given 'CURRENCY[:PROVIDER[:MODE[:WIDTHxHEIGHT]]]' (e.g. 'btc', 'eth::log', 'ltc:faker:scale:60x12')")
+            .takes_value(true)
+            .multiple(true)
+            .number_of_values(1)
+            .conflicts_with("compare"))
+        .arg(Arg::with_name("columns")
+            .long("columns")
+            .help("Sets the number of panes per row of the dashboard")
+            .takes_value(true))
+        .arg(Arg::with_name("y-min")
+            .long("y-min")
+            .help("Fix the bottom of the y-axis to the given price (or percent with '--percent' and '--anchor'); hit 'f' to fix the visible range, 'a' to fit it to the data again and '+'/'-' to zoom")
+            .takes_value(true)
+            .allow_hyphen_values(true))
+        .arg(Arg::with_name("y-max")
+            .long("y-max")
+            .help("Fix the top of the y-axis to the given price (or percent with '--percent' and '--anchor')")
+            .takes_value(true)
+            .allow_hyphen_values(true))
+        .arg(Arg::with_name("candles")
+            .long("candles")
+            .help("Aggregate the rates into open/high/low/close candles of the given width (e.g. '1m', '5m', '1h') and chart them")
             .takes_value(true))
         .arg(Arg::with_name("history-size")
             .long("history-size")
@@ -186,6 +591,29 @@ fn main() {
             .long("fill")
             .help("Sets the chart's fill character")
             .takes_value(true))
+        .arg(Arg::with_name("area-fill")
+            .long("area-fill")
+            .help("Sets the character used to fill the area below the points in the area style")
+            .takes_value(true))
+        .arg(Arg::with_name("volume")
+            .long("volume")
+            .help("Draws the trading volume in a histogram below the chart"))
+        .arg(Arg::with_name("reference")
+            .long("reference")
+            .help("Draws a horizontal line at 'open' (the first rate), 'average' or 'PRICE[:LABEL]' (e.g. '17000:entry')")
+            .takes_value(true)
+            .multiple(true)
+            .number_of_values(1))
+        .arg(Arg::with_name("indicator")
+            .long("indicator")
+            .help("Draws the indicator 'sma:PERIOD', 'ema:PERIOD' or 'bollinger:PERIOD[:WIDTH]' (e.g. 'sma:20') as an extra series (points and line styles only)")
+            .takes_value(true)
+            .multiple(true)
+            .number_of_values(1))
+        .arg(Arg::with_name("oscillator")
+            .long("oscillator")
+            .help("Draws the oscillator 'rsi[:PERIOD]' or 'macd[:FAST:SLOW:SIGNAL]' (e.g. 'rsi:14') in a panel below the chart")
+            .takes_value(true))
         .arg(Arg::with_name("interval")
             .long("interval")
             .short("i")
@@ -195,13 +623,21 @@ fn main() {
             .long("provider")
             .help(&format!("Fetch rates from the given provider [{}]", get_all_providers()))
             .takes_value(true))
+        .arg(Arg::with_name("quote")
+            .long("quote")
+            .help("Sets the quote currency used for the chart and the footer (e.g. USD, EUR, GBP, CHF)")
+            .takes_value(true))
+        .arg(Arg::with_name("currencies")
+            .long("currencies")
+            .help("Load additional currency definitions from the given JSON file")
+            .takes_value(true))
         .get_matches();
 
 
     let mut keyboard_listener = ui::keyboard::KeyboardListener::new();
-    keyboard_listener.add_listener('q', |_| {
-        return true;
-    });
+    for key in &['q', 'f', 'a', '+', '-'] {
+        keyboard_listener.add_listener(*key, get_key_action);
+    }
     signal_handler::register(signal_handler::Signal::SIGINT, received_signal);
 
     let interval = time::Duration::from_millis(get_interval(&matches) / 5);
@@ -209,19 +645,26 @@ fn main() {
     let space = get_chart_fill(&matches);
     let value = get_value(&matches);
     let provider = get_provider(&matches);
-    let currency = get_currency(&matches);
+    let quote = get_quote(&matches);
+    let mut registry = get_currency_registry(&matches);
+    let panes = get_panes(&matches, &mut registry, &provider, &quote);
 
-    let chart = chart::Chart::new(
-        get_chart_width(&matches),
-        get_chart_height(&matches),
-        0,
-        10,
-        get_mode(&matches),
-    );
+    let mut view = if panes.is_empty() {
+        let currency = get_currency(&matches, &mut registry, &provider, &quote);
+        let compared_currencies = get_compared_currencies(&matches, &mut registry, &provider, &quote);
+        let mut chart = build_chart(&matches, get_chart_width(&matches), get_chart_height(&matches), get_mode(&matches));
+        if !compared_currencies.is_empty() {
+            // The series can only be compared relative to their own start
+            chart.y_axis = chart::YAxis::PercentChange(None);
+        }
 
-    let mut printer = match rate_printer::RatePrinter::new(chart, value, &provider, &fill, &space, get_history_size(&matches)) {
-        Ok(p) => p,
-        Err(error) => error!("{}", error),
+        let mut printer = build_printer(&matches, chart, value, &provider, &quote, &fill, &space);
+        for compared_currency in compared_currencies {
+            printer.compare_with(compared_currency);
+        }
+        View::Single(Box::new(printer), currency)
+    } else {
+        View::Dashboard(build_dashboard(&matches, &panes, value, &provider, &quote, &fill, &space))
     };
     let mut run_number = 0;
     let mut error: Option<self::ui::Error> = None;
@@ -229,15 +672,26 @@ fn main() {
 
     loop {
         if run_number == 0 || run_number % 5 == 0 {
-            if let Err(e) = printer.get_and_print_rates(currency) {
+            if let Err(e) = view.get_and_print_rates() {
                 error = Some(e);
                 break;
             }
         }
-        let result: Vec<bool> = keyboard_listener.listen();
-        if !result.is_empty() && result.into_iter().find(|i| *i == true).is_some() {
+        let actions: Vec<KeyAction> = keyboard_listener.listen();
+        if actions.contains(&KeyAction::Quit) {
             break;
         }
+        for action in actions {
+            for printer in view.printers_mut() {
+                match action {
+                    KeyAction::FixYRange => printer.fix_y_range(),
+                    KeyAction::ResetYRange => printer.reset_y_range(),
+                    KeyAction::ZoomIn => printer.zoom_y_range(0.5),
+                    KeyAction::ZoomOut => printer.zoom_y_range(2.0),
+                    KeyAction::Quit | KeyAction::None => {}
+                }
+            }
+        }
         run_number += 1;
 
         thread::sleep(interval);
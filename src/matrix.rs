@@ -8,6 +8,21 @@ pub trait PointTrait: Clone {
     fn x(&self) -> CoordinatePrecision;
     fn y(&self) -> CoordinatePrecision;
 
+    /// Return the point's value in data space (e.g. a price), which is quantized into the `y`
+    /// coordinate when the point is drawn
+    ///
+    /// Points which only live on the grid (e.g. cells) return their `y` coordinate
+    fn value(&self) -> f64 {
+        f64::from(self.y())
+    }
+
+    /// Return a copy of the point with the given value
+    ///
+    /// By default the value is rounded into the `y` coordinate
+    fn with_value(&self, value: f64) -> Self {
+        self.with_y(value.round().max(0.0).min(f64::from(CoordinatePrecision::MAX)) as CoordinatePrecision)
+    }
+
     fn with_x(&self, new_x: CoordinatePrecision) -> Self;
     fn with_y(&self, new_y: CoordinatePrecision) -> Self;
     fn with_x_y(&self, new_x: CoordinatePrecision, new_y: CoordinatePrecision) -> Self;
@@ -51,11 +66,12 @@ impl<I: Clone + PointTrait> Matrix<I> {
         Matrix { len, rows }
     }
 
+    #[allow(unused)]
     pub fn map<F>(&self, callback: F) -> Self
         where F: Fn(I) -> I {
         let mut temp_vec: Vec<I> = Vec::with_capacity(self.len);
-        for (_, row) in &self.rows {
-            for (_, point) in row {
+        for row in self.rows.values() {
+            for point in row.values() {
                 temp_vec.push(callback(point.clone()));
             }
         }
@@ -0,0 +1,297 @@
+use chrono::prelude::*;
+use chart::candlestick::CandlePart;
+use chart::candlestick::CandlePointTrait;
+use matrix::PointTrait;
+use ui::CoordinatePrecision;
+use super::Currency;
+use super::Price;
+use super::PriceTrait;
+use super::Rate;
+
+/// Open/high/low/close aggregation of the rates within one time bucket
+#[derive(Debug, Clone)]
+pub struct Candle {
+    pub currency: Currency,
+    /// Start of the bucket
+    pub start: DateTime<Utc>,
+    /// Duration of the bucket in seconds
+    pub width: i64,
+    pub open: Price,
+    pub high: Price,
+    pub low: Price,
+    pub close: Price,
+    /// Number of rates aggregated into the candle
+    pub ticks: usize,
+    /// Time of the latest rate aggregated into the candle, which sets the close
+    pub last_time: DateTime<Utc>,
+    /// Latest trading volume reported for the rates of the candle, if any
+    pub volume: Option<f64>,
+    /// Price the candle stands for when it is drawn as a chart point
+    part: CandlePart,
+
+    /// Price of the part in data space (see `PointTrait::value()`)
+    value: f64,
+    x: CoordinatePrecision,
+    /// Row the candle was placed on, which is only known once the chart scaled its value
+    y: Option<CoordinatePrecision>,
+}
+
+impl Candle {
+    /// Start a new candle of `width` seconds with the given rate
+    pub fn new(rate: &Rate, width: i64) -> Self {
+        let price = rate.price();
+        // Buckets of rates at the edge of the supported time range may start outside of it
+        let start = Utc.timestamp_opt(Self::bucket_start(rate, width), 0).single().unwrap_or_else(|| rate.time());
+
+        Candle {
+            currency: rate.currency.clone(),
+            start,
+            width,
+            open: price,
+            high: price,
+            low: price,
+            close: price,
+            ticks: 1,
+            last_time: rate.time(),
+            volume: rate.volume,
+            part: CandlePart::Close,
+            value: Rate::price_to_value_scaled(price, &rate.currency),
+            x: 0,
+            y: None,
+        }
+    }
+
+    /// Return the timestamp of the bucket the rate belongs to
+    pub fn bucket_start(rate: &Rate, width: i64) -> i64 {
+        rate.time().timestamp().div_euclid(width).saturating_mul(width)
+    }
+
+    /// Check if the rate falls into this candle's bucket
+    pub fn contains(&self, rate: &Rate) -> bool {
+        Self::bucket_start(rate, self.width) == self.start.timestamp()
+    }
+
+    /// Add the rate to the candle
+    ///
+    /// A rate arriving after a later one only extends the high and the low, so the close stays the
+    /// latest price
+    pub fn update(&mut self, rate: &Rate) {
+        let price = rate.price();
+        if price > self.high {
+            self.high = price;
+        }
+        if price < self.low {
+            self.low = price;
+        }
+        self.ticks += 1;
+        if rate.time() < self.last_time {
+            return;
+        }
+        self.last_time = rate.time();
+        self.close = price;
+        self.volume = rate.volume.or(self.volume);
+        self.value = Rate::price_to_value_scaled(price, &self.currency);
+    }
+
+    /// Return `true` if the candle closed above (or at) its open
+    pub fn is_bullish(&self) -> bool {
+        self.close >= self.open
+    }
+
+    /// Return the price of the given part of the candle
+    pub fn price_of(&self, part: CandlePart) -> Price {
+        match part {
+            CandlePart::High => self.high,
+            CandlePart::Low => self.low,
+            CandlePart::Open => self.open,
+            CandlePart::Close | CandlePart::Wick | CandlePart::Body => self.close,
+        }
+    }
+
+    /// Split the candle into high, low, open and close points for the candlestick chart
+    pub fn to_points(&self) -> Vec<Candle> {
+        [CandlePart::High, CandlePart::Low, CandlePart::Open, CandlePart::Close].iter()
+            .map(|part| {
+                let value = Rate::price_to_value_scaled(self.price_of(*part), &self.currency);
+                self.with_part(*part).with_value(value)
+            })
+            .collect()
+    }
+}
+
+impl PriceTrait for Candle {
+    fn currency(&self) -> &Currency {
+        &self.currency
+    }
+
+    /// The price of the part the candle stands for (the closing price by default)
+    fn price(&self) -> Price {
+        self.price_of(self.part)
+    }
+
+    fn time(&self) -> DateTime<Utc> {
+        self.start
+    }
+
+    fn volume(&self) -> Option<f64> {
+        self.volume
+    }
+}
+
+impl PointTrait for Candle {
+    fn x(&self) -> CoordinatePrecision {
+        self.x
+    }
+
+    fn y(&self) -> CoordinatePrecision {
+        self.y.expect("The candle has not been placed on a row (see `PointTrait::value()` for its unscaled value)")
+    }
+
+    fn value(&self) -> f64 {
+        self.value
+    }
+
+    fn with_value(&self, value: f64) -> Self {
+        let mut clone = self.clone();
+        clone.value = value;
+
+        clone
+    }
+
+    fn with_x(&self, new_x: CoordinatePrecision) -> Self {
+        let mut clone = self.clone();
+        clone.x = new_x;
+
+        clone
+    }
+
+    fn with_y(&self, new_y: CoordinatePrecision) -> Self {
+        let mut clone = self.clone();
+        clone.y = Some(new_y);
+
+        clone
+    }
+
+    fn with_x_y(&self, new_x: CoordinatePrecision, new_y: CoordinatePrecision) -> Self {
+        let mut clone = self.clone();
+        clone.x = new_x;
+        clone.y = Some(new_y);
+
+        clone
+    }
+}
+
+impl CandlePointTrait for Candle {
+    fn part(&self) -> CandlePart {
+        self.part
+    }
+
+    fn with_part(&self, part: CandlePart) -> Self {
+        let mut clone = self.clone();
+        clone.part = part;
+
+        clone
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_rate(timestamp: i64, price: &str) -> Rate {
+        Rate::from_price(Currency::bitcoin(), "USD", price.parse().unwrap())
+            .with_source_time(Utc.timestamp_opt(timestamp, 0).single())
+    }
+
+    #[test]
+    fn new_test() {
+        let candle = Candle::new(&build_rate(1_234, "100.5"), 60);
+
+        assert_eq!(1_200, candle.start.timestamp());
+        assert_eq!("100.5", candle.open.to_string());
+        assert_eq!("100.5", candle.high.to_string());
+        assert_eq!("100.5", candle.low.to_string());
+        assert_eq!("100.5", candle.close.to_string());
+        assert_eq!(1, candle.ticks);
+        assert_eq!(100.5, candle.value());
+    }
+
+    #[test]
+    fn new_out_of_range_test() {
+        // The bucket would start before the earliest supported time
+        let time = DateTime::<Utc>::MIN_UTC + ::chrono::Duration::seconds(10);
+        let rate = build_rate(0, "100").with_source_time(Some(time));
+
+        assert_eq!(time, Candle::new(&rate, 1_000_000).start);
+    }
+
+    #[test]
+    fn update_test() {
+        let mut candle = Candle::new(&build_rate(1_200, "100"), 60);
+        candle.update(&build_rate(1_210, "104"));
+        candle.update(&build_rate(1_220, "97"));
+        candle.update(&build_rate(1_259, "99"));
+
+        assert_eq!("100", candle.open.to_string());
+        assert_eq!("104", candle.high.to_string());
+        assert_eq!("97", candle.low.to_string());
+        assert_eq!("99", candle.close.to_string());
+        assert_eq!(4, candle.ticks);
+        assert_eq!(99.0, candle.value());
+        assert!(!candle.is_bullish());
+
+        // A rate arriving late does not replace the close of a later one
+        let mut candle = Candle::new(&build_rate(1_200, "100"), 60);
+        candle.update(&build_rate(1_250, "110"));
+        candle.update(&build_rate(1_210, "105"));
+        candle.update(&build_rate(1_205, "120"));
+
+        assert_eq!("110", candle.close.to_string());
+        assert_eq!("120", candle.high.to_string());
+        assert_eq!(110.0, candle.value());
+        assert_eq!(1_250, candle.last_time.timestamp());
+        assert_eq!(4, candle.ticks);
+    }
+
+    #[test]
+    fn volume_test() {
+        let mut candle = Candle::new(&build_rate(1_200, "100").with_volume(Some(5.0)), 60);
+        assert_eq!(Some(5.0), PriceTrait::volume(&candle));
+
+        candle.update(&build_rate(1_210, "104"));
+        assert_eq!(Some(5.0), PriceTrait::volume(&candle));
+
+        candle.update(&build_rate(1_220, "97").with_volume(Some(7.5)));
+        assert_eq!(Some(7.5), PriceTrait::volume(&candle));
+    }
+
+    #[test]
+    fn to_points_test() {
+        let mut candle = Candle::new(&build_rate(1_200, "100"), 60);
+        candle.update(&build_rate(1_210, "104"));
+        candle.update(&build_rate(1_220, "97"));
+        candle.update(&build_rate(1_259, "99"));
+
+        let points: Vec<(CandlePart, f64, String)> = candle.to_points().iter()
+            .map(|p| (p.part(), p.value(), p.price().to_string()))
+            .collect();
+        assert_eq!(
+            vec![
+                (CandlePart::High, 104.0, "104".to_string()),
+                (CandlePart::Low, 97.0, "97".to_string()),
+                (CandlePart::Open, 100.0, "100".to_string()),
+                (CandlePart::Close, 99.0, "99".to_string()),
+            ],
+            points
+        );
+    }
+
+    #[test]
+    fn contains_test() {
+        let candle = Candle::new(&build_rate(1_200, "100"), 60);
+
+        assert!(candle.contains(&build_rate(1_259, "100")));
+        assert!(!candle.contains(&build_rate(1_260, "100")));
+        assert!(!candle.contains(&build_rate(1_199, "100")));
+    }
+}
@@ -1,49 +1,100 @@
 use serde::{Serialize, Deserialize};
+use super::Price;
 
-#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
-pub enum Currency {
-    Bitcoin,
-    Ethereum,
-    Litecoin,
-    Ripple,
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Currency {
+    symbol: String,
+    name: String,
+    #[serde(default)]
+    aliases: Vec<String>,
+    #[serde(default = "default_precision")]
+    precision: usize,
+}
+
+fn default_precision() -> usize {
+    2
 }
 
 impl Currency {
-    pub fn new<S>(input: S) -> Option<Self> where S: Into<String> {
-        match input.into().to_lowercase().as_str() {
-            "bitcoin" | "btc" => Some(Currency::Bitcoin),
-            "ethereum" | "eth" => Some(Currency::Ethereum),
-            "litecoin" | "ltc" => Some(Currency::Litecoin),
-            "ripple" | "xrp" => Some(Currency::Ripple),
-            _ => None
+    pub fn new<S, N>(symbol: S, name: N, aliases: Vec<String>, precision: usize) -> Self
+        where S: Into<String>, N: Into<String> {
+        Currency {
+            symbol: symbol.into().to_uppercase(),
+            name: name.into(),
+            aliases,
+            precision,
         }
     }
 
-    pub fn name(&self) -> &str {
-        match self {
-            &Currency::Bitcoin => "Bitcoin",
-            &Currency::Ethereum => "Ethereum",
-            &Currency::Litecoin => "Litecoin",
-            &Currency::Ripple => "Ripple",
+    /// Build a currency that is only known by its symbol
+    pub fn from_symbol<S>(symbol: S) -> Self where S: Into<String> {
+        let symbol = symbol.into().to_uppercase();
+
+        Self::new(symbol.clone(), symbol, vec![], default_precision())
+    }
+
+    pub fn bitcoin() -> Self {
+        Self::new("BTC", "Bitcoin", vec![], 2)
+    }
+
+    pub fn ethereum() -> Self {
+        Self::new("ETH", "Ethereum", vec![], 3)
+    }
+
+    pub fn litecoin() -> Self {
+        Self::new("LTC", "Litecoin", vec![], 3)
+    }
+
+    pub fn ripple() -> Self {
+        Self::new("XRP", "Ripple", vec![], 4)
+    }
+
+    /// Return the number of decimals needed to display the given price with at least three significant digits
+    pub fn precision_for_price(price: Price) -> usize {
+        let price = price.to_f64();
+        if price <= 0.0 || price >= 1.0 {
+            default_precision()
+        } else {
+            (2 - price.log10().floor() as i32) as usize
         }
     }
 
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
     pub fn symbol(&self) -> &str {
-        match self {
-            &Currency::Bitcoin => "BTC",
-            &Currency::Ethereum => "ETH",
-            &Currency::Litecoin => "LTC",
-            &Currency::Ripple => "XRP",
-        }
+        &self.symbol
+    }
+
+    #[allow(unused)]
+    pub fn aliases(&self) -> &[String] {
+        &self.aliases
+    }
+
+    /// Number of decimals used when displaying prices of this currency
+    pub fn precision(&self) -> usize {
+        self.precision
+    }
+
+    /// Check if the input matches the symbol, name or one of the aliases (case-insensitive)
+    pub fn matches(&self, input: &str) -> bool {
+        let input = input.to_lowercase();
+
+        self.symbol.to_lowercase() == input
+            || self.name.to_lowercase() == input
+            || self.aliases.iter().any(|alias| alias.to_lowercase() == input)
     }
 
     pub fn scale(&self) -> f32 {
-        match self {
-            &Currency::Bitcoin => 1.0,
-            &Currency::Ethereum => 10.0,
-            &Currency::Litecoin => 10.0,
-            &Currency::Ripple => 100.0,
-        }
+        10.0f32.powi(self.precision as i32 - 2).max(1.0)
+    }
+}
+
+/// Currencies are identified by their symbol
+impl PartialEq for Currency {
+    fn eq(&self, other: &Currency) -> bool {
+        self.symbol == other.symbol
     }
 }
 
@@ -53,46 +104,62 @@ mod tests {
     use super::*;
 
     #[test]
-    fn from_str_test() {
-        assert_eq!(Currency::Bitcoin, Currency::new("BITCOIN").unwrap());
-        assert_eq!(Currency::Bitcoin, Currency::new("BitCoin").unwrap());
-        assert_eq!(Currency::Bitcoin, Currency::new("Bitcoin").unwrap());
-        assert_eq!(Currency::Bitcoin, Currency::new("bitcoin").unwrap());
-        assert_eq!(Currency::Bitcoin, Currency::new("BTC").unwrap());
-        assert_eq!(Currency::Bitcoin, Currency::new("btc").unwrap());
-
-        assert_eq!(Currency::Ethereum, Currency::new("ETHEREUM").unwrap());
-        assert_eq!(Currency::Ethereum, Currency::new("Ethereum").unwrap());
-        assert_eq!(Currency::Ethereum, Currency::new("ethereum").unwrap());
-        assert_eq!(Currency::Ethereum, Currency::new("ETH").unwrap());
-        assert_eq!(Currency::Ethereum, Currency::new("eth").unwrap());
-
-        assert_eq!(Currency::Litecoin, Currency::new("LITECOIN").unwrap());
-        assert_eq!(Currency::Litecoin, Currency::new("Litecoin").unwrap());
-        assert_eq!(Currency::Litecoin, Currency::new("litecoin").unwrap());
-        assert_eq!(Currency::Litecoin, Currency::new("LTC").unwrap());
-        assert_eq!(Currency::Litecoin, Currency::new("ltc").unwrap());
-
-        assert_eq!(Currency::Ripple, Currency::new("RIPPLE").unwrap());
-        assert_eq!(Currency::Ripple, Currency::new("Ripple").unwrap());
-        assert_eq!(Currency::Ripple, Currency::new("RIPPLE").unwrap());
-        assert_eq!(Currency::Ripple, Currency::new("XRP").unwrap());
-        assert_eq!(Currency::Ripple, Currency::new("xrp").unwrap());
+    fn matches_test() {
+        let bitcoin = Currency::bitcoin();
+        assert!(bitcoin.matches("BITCOIN"));
+        assert!(bitcoin.matches("BitCoin"));
+        assert!(bitcoin.matches("Bitcoin"));
+        assert!(bitcoin.matches("bitcoin"));
+        assert!(bitcoin.matches("BTC"));
+        assert!(bitcoin.matches("btc"));
+        assert!(!bitcoin.matches("eth"));
+
+        let dogecoin = Currency::new("doge", "Dogecoin", vec!["Much Wow".to_string()], 6);
+        assert!(dogecoin.matches("DOGE"));
+        assert!(dogecoin.matches("dogecoin"));
+        assert!(dogecoin.matches("much wow"));
+        assert!(!dogecoin.matches("wow"));
     }
 
     #[test]
     fn name_test() {
-        assert_eq!("Bitcoin", Currency::Bitcoin.name());
-        assert_eq!("Ethereum", Currency::Ethereum.name());
-        assert_eq!("Litecoin", Currency::Litecoin.name());
-        assert_eq!("Ripple", Currency::Ripple.name());
+        assert_eq!("Bitcoin", Currency::bitcoin().name());
+        assert_eq!("Ethereum", Currency::ethereum().name());
+        assert_eq!("Litecoin", Currency::litecoin().name());
+        assert_eq!("Ripple", Currency::ripple().name());
+        assert_eq!("DOGE", Currency::from_symbol("doge").name());
     }
 
     #[test]
     fn symbol_test() {
-        assert_eq!("BTC", Currency::Bitcoin.symbol());
-        assert_eq!("ETH", Currency::Ethereum.symbol());
-        assert_eq!("LTC", Currency::Litecoin.symbol());
-        assert_eq!("XRP", Currency::Ripple.symbol());
+        assert_eq!("BTC", Currency::bitcoin().symbol());
+        assert_eq!("ETH", Currency::ethereum().symbol());
+        assert_eq!("LTC", Currency::litecoin().symbol());
+        assert_eq!("XRP", Currency::ripple().symbol());
+        assert_eq!("DOGE", Currency::from_symbol("doge").symbol());
+    }
+
+    #[test]
+    fn scale_test() {
+        assert_eq!(1.0, Currency::bitcoin().scale());
+        assert_eq!(10.0, Currency::ethereum().scale());
+        assert_eq!(10.0, Currency::litecoin().scale());
+        assert_eq!(100.0, Currency::ripple().scale());
+        assert_eq!(1.0, Currency::new("X", "X", vec![], 0).scale());
+    }
+
+    #[test]
+    fn precision_for_price_test() {
+        assert_eq!(2, Currency::precision_for_price(Price::from_f64(19234.56)));
+        assert_eq!(2, Currency::precision_for_price(Price::from_f64(1.0)));
+        assert_eq!(3, Currency::precision_for_price(Price::from_f64(0.5)));
+        assert_eq!(5, Currency::precision_for_price(Price::from_f64(0.005)));
+        assert_eq!(2, Currency::precision_for_price(Price::zero()));
+    }
+
+    #[test]
+    fn eq_test() {
+        assert_eq!(Currency::bitcoin(), Currency::from_symbol("btc"));
+        assert_ne!(Currency::bitcoin(), Currency::ethereum());
     }
 }
@@ -0,0 +1,186 @@
+use std::fs;
+use serde_json;
+use super::Currency;
+use super::Rate;
+use super::RegistryError;
+
+/// Collection of the currencies known to rcoin
+///
+/// The registry starts with a small set of built-in currencies and can be extended with entries
+/// from a JSON configuration file or with the currencies found in a provider's listing
+#[derive(Debug, Clone)]
+pub struct CurrencyRegistry {
+    currencies: Vec<Currency>,
+    /// Providers whose listing was registered
+    listed_providers: Vec<String>,
+}
+
+impl CurrencyRegistry {
+    pub fn new() -> Self {
+        CurrencyRegistry { currencies: vec![], listed_providers: vec![] }
+    }
+
+    /// Build a registry containing the built-in currencies
+    pub fn with_defaults() -> Self {
+        let mut registry = Self::new();
+        registry.register(Currency::bitcoin());
+        registry.register(Currency::ethereum());
+        registry.register(Currency::litecoin());
+        registry.register(Currency::ripple());
+
+        registry
+    }
+
+    /// Register the currency, replacing an existing entry with the same symbol
+    pub fn register(&mut self, currency: Currency) {
+        match self.currencies.iter().position(|c| *c == currency) {
+            Some(index) => self.currencies[index] = currency,
+            None => self.currencies.push(currency),
+        }
+    }
+
+    /// Register the currency only if no entry with the same symbol exists
+    pub fn register_missing(&mut self, currency: Currency) {
+        if !self.currencies.contains(&currency) {
+            self.currencies.push(currency);
+        }
+    }
+
+    /// Register the currencies of a provider listing without overwriting existing entries
+    pub fn register_rates(&mut self, rates: &[Rate]) {
+        for rate in rates {
+            self.register_missing(rate.currency.clone());
+        }
+    }
+
+    /// Register the currencies of the provider's listing (see `register_rates()`) and remember the provider
+    /// as listed
+    pub fn register_listing(&mut self, provider: &str, rates: &[Rate]) {
+        self.register_rates(rates);
+        if !self.has_listing(provider) {
+            self.listed_providers.push(provider.to_lowercase());
+        }
+    }
+
+    /// Return if the listing of the provider was registered already
+    pub fn has_listing(&self, provider: &str) -> bool {
+        self.listed_providers.contains(&provider.to_lowercase())
+    }
+
+    /// Register the currencies defined in the JSON file at `path`
+    ///
+    /// The file must contain a list of objects with the keys `symbol`, `name` and the optional
+    /// `aliases` and `precision`
+    pub fn load_file(&mut self, path: &str) -> Result<(), RegistryError> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) => return Err(RegistryError::new(format!("Could not read currency file '{}': {}", path, e))),
+        };
+
+        self.load_str(&content)
+    }
+
+    /// Register the currencies defined in the JSON string
+    pub fn load_str(&mut self, content: &str) -> Result<(), RegistryError> {
+        let deserialized_result: serde_json::Result<Vec<Currency>> = serde_json::from_str(content);
+
+        match deserialized_result {
+            Ok(currencies) => {
+                for currency in currencies {
+                    self.register(currency);
+                }
+                Ok(())
+            }
+            Err(e) => Err(RegistryError::new(format!("Invalid currency definition: {}", e))),
+        }
+    }
+
+    /// Look up the currency matching the given symbol, name or alias
+    pub fn get(&self, input: &str) -> Option<&Currency> {
+        self.currencies.iter().find(|c| c.matches(input))
+    }
+
+    #[allow(unused)]
+    pub fn len(&self) -> usize {
+        self.currencies.len()
+    }
+}
+
+impl Default for CurrencyRegistry {
+    fn default() -> Self {
+        Self::with_defaults()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rate::Price;
+
+    #[test]
+    fn get_test() {
+        let registry = CurrencyRegistry::with_defaults();
+
+        assert_eq!(Currency::bitcoin(), *registry.get("BITCOIN").unwrap());
+        assert_eq!(Currency::bitcoin(), *registry.get("btc").unwrap());
+        assert_eq!(Currency::ethereum(), *registry.get("Ethereum").unwrap());
+        assert_eq!(Currency::litecoin(), *registry.get("LTC").unwrap());
+        assert_eq!(Currency::ripple(), *registry.get("ripple").unwrap());
+        assert!(registry.get("doge").is_none());
+    }
+
+    #[test]
+    fn load_str_test() {
+        let mut registry = CurrencyRegistry::with_defaults();
+        let result = registry.load_str(r#"[
+            {"symbol": "DOGE", "name": "Dogecoin", "aliases": ["xdg"], "precision": 6},
+            {"symbol": "BTC", "name": "Bitcoin", "precision": 0}
+        ]"#);
+        assert!(result.is_ok());
+        assert_eq!(5, registry.len());
+
+        let dogecoin = registry.get("xdg").unwrap();
+        assert_eq!("DOGE", dogecoin.symbol());
+        assert_eq!(6, dogecoin.precision());
+
+        // Entries from the file replace the built-in definitions
+        assert_eq!(0, registry.get("bitcoin").unwrap().precision());
+    }
+
+    #[test]
+    fn load_str_default_precision_test() {
+        let mut registry = CurrencyRegistry::new();
+        assert!(registry.load_str(r#"[{"symbol": "ADA", "name": "Cardano"}]"#).is_ok());
+        assert_eq!(2, registry.get("cardano").unwrap().precision());
+    }
+
+    #[test]
+    fn load_str_invalid_test() {
+        assert!(CurrencyRegistry::new().load_str(r#"[{"name": "Cardano"}]"#).is_err());
+        assert!(CurrencyRegistry::new().load_str("{").is_err());
+    }
+
+    #[test]
+    fn register_rates_test() {
+        let mut registry = CurrencyRegistry::with_defaults();
+        registry.register_rates(&[
+            Rate::from_price(Currency::new("BTC", "Bitcoin", vec![], 5), "USD", Price::from_f64(1.0)),
+            Rate::from_price(Currency::new("DOGE", "Dogecoin", vec![], 6), "USD", Price::from_f64(1.0)),
+        ]);
+
+        assert_eq!(5, registry.len());
+        assert_eq!(2, registry.get("btc").unwrap().precision());
+        assert_eq!(6, registry.get("dogecoin").unwrap().precision());
+    }
+
+    #[test]
+    fn register_listing_test() {
+        let mut registry = CurrencyRegistry::with_defaults();
+        assert!(!registry.has_listing("faker"));
+
+        registry.register_listing("Faker", &[Rate::from_price(Currency::new("DOGE", "Dogecoin", vec![], 6), "USD", Price::from_f64(1.0))]);
+        assert!(registry.has_listing("faker"));
+        assert!(!registry.has_listing("coindesk"));
+        assert_eq!(6, registry.get("doge").unwrap().precision());
+    }
+}
@@ -0,0 +1,183 @@
+use super::Price;
+use super::Rate;
+
+/// Number of standard deviations between the Bollinger bands and their middle if none is given
+pub const DEFAULT_BOLLINGER_WIDTH: f64 = 2.0;
+
+/// Technical indicator computed over the prices of a rate series, parsed from `NAME:PERIOD`
+///
+/// The Bollinger bands take the number of standard deviations as an optional third part
+/// (e.g. "bollinger:20:2.5")
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Indicator {
+    /// Simple moving average over the given number of rates
+    Sma(usize),
+    /// Exponential moving average over the given number of rates
+    Ema(usize),
+    /// Simple moving average with bands the given number of standard deviations above and below it
+    Bollinger(usize, f64),
+}
+
+impl Indicator {
+    pub fn from_str(input: &str) -> Result<Self, ()> {
+        let parts: Vec<&str> = input.split(':').map(str::trim).collect();
+        let period = match parts.get(1).map(|period| period.parse::<usize>()) {
+            Some(Ok(period)) if period > 0 => period,
+            _ => return Err(()),
+        };
+
+        match (parts[0].to_lowercase().as_ref(), parts.len()) {
+            ("sma", 2) => Ok(Indicator::Sma(period)),
+            ("ema", 2) => Ok(Indicator::Ema(period)),
+            ("bollinger" | "bb", 2) => Ok(Indicator::Bollinger(period, DEFAULT_BOLLINGER_WIDTH)),
+            ("bollinger" | "bb", 3) => match parts[2].parse::<f64>() {
+                Ok(width) if width > 0.0 && width.is_finite() => Ok(Indicator::Bollinger(period, width)),
+                _ => Err(()),
+            },
+            _ => Err(()),
+        }
+    }
+
+    /// Return the name of the indicator (e.g. "SMA(20)")
+    pub fn name(&self) -> String {
+        match *self {
+            Indicator::Sma(period) => format!("SMA({})", period),
+            Indicator::Ema(period) => format!("EMA({})", period),
+            Indicator::Bollinger(period, width) => format!("BB({},{})", period, width),
+        }
+    }
+
+    /// Return the lines of the indicator as values aligned with the prices
+    ///
+    /// Averages consist of one line, the Bollinger bands of the upper band, the middle and the lower band.
+    /// Values are missing until enough prices are known
+    pub fn compute(&self, prices: &[f64]) -> Vec<Vec<Option<f64>>> {
+        match *self {
+            Indicator::Sma(period) => vec![sma(prices, period)],
+            Indicator::Ema(period) => vec![ema(prices, period)],
+            Indicator::Bollinger(period, width) => {
+                let middle = sma(prices, period);
+                let deviations = standard_deviation(prices, period);
+                let band = |sign: f64| -> Vec<Option<f64>> {
+                    middle.iter().zip(&deviations)
+                        .map(|(middle, deviation)| Some(middle.as_ref()? + sign * width * deviation.as_ref()?))
+                        .collect()
+                };
+
+                vec![band(1.0), middle.clone(), band(-1.0)]
+            }
+        }
+    }
+
+    /// Return the lines of the indicator as rates, each a copy of the rate it was computed at
+    pub fn apply(&self, rates: &[Rate]) -> Vec<Vec<Rate>> {
+        let prices: Vec<f64> = rates.iter().map(|rate| rate.price().to_f64()).collect();
+
+        self.compute(&prices).iter()
+            .map(|line| {
+                rates.iter().zip(line)
+                    .filter_map(|(rate, value)| Some(rate.with_price(Price::from_f64((*value)?))))
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// Return the mean of the last `period` values at each position
+pub fn sma(values: &[f64], period: usize) -> Vec<Option<f64>> {
+    windows(values, period, |window| window.iter().sum::<f64>() / window.len() as f64)
+}
+
+/// Return the exponential moving average at each position, starting with the simple moving average of
+/// the first `period` values
+pub fn ema(values: &[f64], period: usize) -> Vec<Option<f64>> {
+    let alpha = 2.0 / (period as f64 + 1.0);
+    let mut result = vec![None; values.len()];
+    if period == 0 || values.len() < period {
+        return result;
+    }
+
+    let mut average = values[..period].iter().sum::<f64>() / period as f64;
+    result[period - 1] = Some(average);
+    for (index, value) in values.iter().enumerate().skip(period) {
+        average += alpha * (value - average);
+        result[index] = Some(average);
+    }
+
+    result
+}
+
+/// Return the (population) standard deviation of the last `period` values at each position
+pub fn standard_deviation(values: &[f64], period: usize) -> Vec<Option<f64>> {
+    windows(values, period, |window| {
+        let mean = window.iter().sum::<f64>() / window.len() as f64;
+        let variance = window.iter().map(|value| (value - mean).powi(2)).sum::<f64>() / window.len() as f64;
+
+        variance.sqrt()
+    })
+}
+
+fn windows<F>(values: &[f64], period: usize, callback: F) -> Vec<Option<f64>>
+    where F: Fn(&[f64]) -> f64 {
+    if period == 0 {
+        return vec![None; values.len()];
+    }
+
+    (0..values.len())
+        .map(|index| if index + 1 >= period { Some(callback(&values[index + 1 - period..=index])) } else { None })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rate::Currency;
+
+    #[test]
+    fn from_str_test() {
+        assert_eq!(Ok(Indicator::Sma(20)), Indicator::from_str("sma:20"));
+        assert_eq!(Ok(Indicator::Ema(12)), Indicator::from_str("EMA:12"));
+        assert_eq!(Ok(Indicator::Bollinger(20, 2.0)), Indicator::from_str("bollinger:20"));
+        assert_eq!(Ok(Indicator::Bollinger(10, 2.5)), Indicator::from_str("bb:10:2.5"));
+        assert!(Indicator::from_str("sma").is_err());
+        assert!(Indicator::from_str("sma:0").is_err());
+        assert!(Indicator::from_str("sma:20:2").is_err());
+        assert!(Indicator::from_str("bb:20:-1").is_err());
+        assert!(Indicator::from_str("rsi:14").is_err());
+    }
+
+    #[test]
+    fn sma_test() {
+        assert_eq!(vec![None, None, Some(2.0), Some(3.0), Some(4.0)], sma(&[1.0, 2.0, 3.0, 4.0, 5.0], 3));
+        assert_eq!(vec![None, None], sma(&[1.0, 2.0], 3));
+    }
+
+    #[test]
+    fn ema_test() {
+        // The average starts with the SMA of the first two values, then each value weighs 2/3
+        assert_eq!(vec![None, Some(2.0), Some(4.0), Some(4.0)], ema(&[1.0, 3.0, 5.0, 4.0], 2));
+        assert_eq!(vec![None], ema(&[1.0], 2));
+    }
+
+    #[test]
+    fn bollinger_test() {
+        let lines = Indicator::Bollinger(2, 2.0).compute(&[1.0, 3.0, 3.0]);
+
+        assert_eq!(vec![None, Some(4.0), Some(3.0)], lines[0]);
+        assert_eq!(vec![None, Some(2.0), Some(3.0)], lines[1]);
+        assert_eq!(vec![None, Some(0.0), Some(3.0)], lines[2]);
+    }
+
+    #[test]
+    fn apply_test() {
+        let rates: Vec<Rate> = ["100", "104", "99"].iter()
+            .map(|price| Rate::from_price(Currency::bitcoin(), "USD", price.parse().unwrap()))
+            .collect();
+        let lines = Indicator::Sma(2).apply(&rates);
+
+        assert_eq!(1, lines.len());
+        let prices: Vec<String> = lines[0].iter().map(|rate| rate.price().to_string()).collect();
+        assert_eq!(vec!["102", "101.5"], prices);
+        assert_eq!(rates[1].time(), lines[0][0].time());
+    }
+}
@@ -1,41 +1,210 @@
 mod rate_series;
 mod currency;
+mod currency_registry;
+mod registry_error;
+mod price;
+mod candle;
+mod indicator;
+mod oscillator;
 
 pub use self::rate_series::RateSeries;
 pub use self::currency::Currency;
+pub use self::currency_registry::CurrencyRegistry;
+pub use self::registry_error::RegistryError;
+pub use self::price::Price;
+pub use self::candle::Candle;
+pub use self::indicator::Indicator;
+pub use self::oscillator::Oscillator;
+use std::collections::BTreeMap;
+use chrono::prelude::*;
+use chart::cell::Cell;
 use matrix::PointTrait;
 use ui::CoordinatePrecision;
 use serde::{Serialize, Deserialize};
 
+/// Quote currency used if none is specified
+pub const DEFAULT_QUOTE: &str = "USD";
+
+/// Number of value units per unit of the ratio between a price and its anchor (one per 0.01%)
+pub const RELATIVE_SCALE: f64 = 10_000.0;
+
+/// Map of quote currency codes (e.g. "USD", "GBP") to prices
+pub type PriceMap = BTreeMap<String, Price>;
+
+/// Common interface of the price records (raw rates and candles) that can be charted
+pub trait PriceTrait {
+    fn currency(&self) -> &Currency;
+    fn price(&self) -> Price;
+    fn time(&self) -> DateTime<Utc>;
+    /// Return the trading volume reported by the provider, if any
+    fn volume(&self) -> Option<f64>;
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Rate {
     pub currency: Currency,
-    pub price_btc: f32,
-    pub price_usd: f32,
-    pub price_eur: f32,
+    pub prices: PriceMap,
+    quote: String,
+    /// Time the rate was fetched
+    pub fetched_at: DateTime<Utc>,
+    /// Time the provider reported for the rate, if any
+    pub source_time: Option<DateTime<Utc>>,
+    /// Trading volume reported by the provider (e.g. over the last 24 hours), if any
+    pub volume: Option<f64>,
 
+    /// Price in data space (see `PointTrait::value()`)
+    value: f64,
     x: CoordinatePrecision,
-    y: CoordinatePrecision,
+    /// Row the rate was placed on, which is only known once the chart scaled its value
+    y: Option<CoordinatePrecision>,
 }
 
 impl Rate {
-    pub fn new(currency: Currency, price_usd: f32, price_eur: f32) -> Self {
+    /// Build a new rate with the prices for each quote currency
+    ///
+    /// The price in `quote` drives the chart value
+    pub fn new<S>(currency: Currency, quote: S, prices: PriceMap) -> Self where S: Into<String> {
+        let quote = quote.into().to_uppercase();
+        let value = Self::price_to_value_scaled(
+            prices.get(&quote).cloned().unwrap_or_default(),
+            &currency,
+        );
         Rate {
             currency,
-            price_btc: 1.0,
-            price_usd: price_usd.clone(),
-            price_eur: price_eur.clone(),
+            prices,
+            quote,
+            fetched_at: Utc::now(),
+            source_time: None,
+            volume: None,
+            value,
             x: 0,
-            y: Self::price_to_coordinate_scaled(price_usd, &currency),
+            y: None,
         }
     }
 
-    pub fn price_to_coordinate(price: f32) -> CoordinatePrecision {
-        price.round() as CoordinatePrecision
+    /// Build a new rate with a single price in the given quote currency
+    #[allow(unused)]
+    pub fn from_price<S>(currency: Currency, quote: S, price: Price) -> Self where S: Into<String> {
+        let quote = quote.into().to_uppercase();
+        let mut prices = PriceMap::new();
+        prices.insert(quote.clone(), price);
+
+        Rate::new(currency, quote, prices)
+    }
+
+    /// Return a copy of the rate which is driven by the price in the given quote currency
+    pub fn with_quote<S>(&self, quote: S) -> Self where S: Into<String> {
+        let mut rate = Rate::new(self.currency.clone(), quote, self.prices.clone());
+        rate.fetched_at = self.fetched_at;
+        rate.source_time = self.source_time;
+        rate.volume = self.volume;
+
+        rate.with_x(self.x)
+    }
+
+    /// Return a copy of the rate with the given price in its quote currency
+    ///
+    /// The prices in other quote currencies are dropped, since they no longer match
+    pub fn with_price(&self, price: Price) -> Self {
+        let mut rate = Rate::from_price(self.currency.clone(), self.quote.clone(), price);
+        rate.fetched_at = self.fetched_at;
+        rate.source_time = self.source_time;
+
+        rate.with_x(self.x)
+    }
+
+    /// Return a copy of the rate with the time reported by the provider
+    pub fn with_source_time(&self, source_time: Option<DateTime<Utc>>) -> Self {
+        let mut clone = self.clone();
+        clone.source_time = source_time;
+
+        clone
+    }
+
+    /// Return a copy of the rate with the trading volume reported by the provider
+    pub fn with_volume(&self, volume: Option<f64>) -> Self {
+        let mut clone = self.clone();
+        clone.volume = volume;
+
+        clone
+    }
+
+    /// Return the time the rate belongs to
+    ///
+    /// This is the provider's time if it is known, otherwise the time the rate was fetched
+    pub fn time(&self) -> DateTime<Utc> {
+        self.source_time.unwrap_or(self.fetched_at)
+    }
+
+    /// Return the time passed between the rate's time and `now`
+    pub fn age(&self, now: DateTime<Utc>) -> ::chrono::Duration {
+        now.signed_duration_since(self.time())
+    }
+
+    /// Return the quote currency that drives this rate
+    pub fn quote(&self) -> &str {
+        &self.quote
+    }
+
+    /// Return the price in the rate's quote currency
+    pub fn price(&self) -> Price {
+        self.price_in(&self.quote).unwrap_or_default()
+    }
+
+    /// Return the price in the given quote currency, if it is known
+    pub fn price_in(&self, quote: &str) -> Option<Price> {
+        self.prices.get(&quote.to_uppercase()).cloned()
+    }
+
+    /// Return the value for the price, scaled to the currency's precision (see `Currency::scale()`)
+    fn price_to_value_scaled(price: Price, currency: &Currency) -> f64 {
+        price.to_f64() * f64::from(currency.scale())
+    }
+
+    /// Return the value for the change of the price from the anchor price (see `RELATIVE_SCALE`)
+    pub fn price_to_value_relative(price: Price, anchor: Price) -> f64 {
+        if anchor.to_f64() <= 0.0 {
+            return 0.0;
+        }
+
+        (price.to_f64() / anchor.to_f64() - 1.0) * RELATIVE_SCALE
+    }
+}
+
+impl PriceTrait for Rate {
+    fn currency(&self) -> &Currency {
+        &self.currency
+    }
+
+    fn price(&self) -> Price {
+        Rate::price(self)
+    }
+
+    fn time(&self) -> DateTime<Utc> {
+        Rate::time(self)
     }
 
-    fn price_to_coordinate_scaled(price: f32, currency: &Currency) -> CoordinatePrecision {
-        (price * currency.scale()).round() as CoordinatePrecision
+    fn volume(&self) -> Option<f64> {
+        self.volume
+    }
+}
+
+/// Chart cells report the price of the point they were built from
+impl<P: PriceTrait + PointTrait, K: Copy> PriceTrait for Cell<P, K> {
+    fn currency(&self) -> &Currency {
+        self.point.currency()
+    }
+
+    fn price(&self) -> Price {
+        self.point.price()
+    }
+
+    fn time(&self) -> DateTime<Utc> {
+        self.point.time()
+    }
+
+    fn volume(&self) -> Option<f64> {
+        self.point.volume()
     }
 }
 
@@ -45,7 +214,18 @@ impl PointTrait for Rate {
     }
 
     fn y(&self) -> CoordinatePrecision {
-        self.y
+        self.y.expect("The rate has not been placed on a row (see `PointTrait::value()` for its unscaled value)")
+    }
+
+    fn value(&self) -> f64 {
+        self.value
+    }
+
+    fn with_value(&self, value: f64) -> Self {
+        let mut clone = self.clone();
+        clone.value = value;
+
+        clone
     }
 
     fn with_x(&self, new_x: CoordinatePrecision) -> Self {
@@ -57,7 +237,7 @@ impl PointTrait for Rate {
 
     fn with_y(&self, new_y: CoordinatePrecision) -> Self {
         let mut clone = self.clone();
-        clone.y = new_y;
+        clone.y = Some(new_y);
 
         clone
     }
@@ -65,48 +245,98 @@ impl PointTrait for Rate {
     fn with_x_y(&self, new_x: CoordinatePrecision, new_y: CoordinatePrecision) -> Self {
         let mut clone = self.clone();
         clone.x = new_x;
-        clone.y = new_y;
+        clone.y = Some(new_y);
 
         clone
     }
 }
 
-//impl<'a> PointTrait for &'a Rate {
-//    fn x(&self) -> usize {
-//        self.x
-//    }
-//
-//    fn y(&self) -> usize {
-//        self.y
-//    }
-//
-//    fn with_x(&self, new_x: usize) -> Self {
-//        let mut clone = Rate {
-//            id: self.id.clone(),
-//            name: self.name.clone(),
-//            symbol: self.symbol.clone(),
-//            price_btc: self.price_btc,
-//            price_usd: self.price_usd,
-//            price_eur: self.price_eur,
-//            x: new_x,
-//            y: self.y,
-//        };
-//
-//        &clone
-//    }
-//
-//    fn with_y(&self, new_y: usize) -> Self {
-//        let mut clone = self.clone();
-//        clone.y = new_y;
-//
-//        clone
-//    }
-//
-//    fn with_x_y(&self, new_x: usize, new_y: usize) -> Self {
-//        let mut clone = self.clone();
-//        clone.x = new_x;
-//        clone.y = new_y;
-//
-//        clone
-//    }
-//}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_prices() -> PriceMap {
+        let mut prices = PriceMap::new();
+        prices.insert("USD".to_string(), "10.05".parse().unwrap());
+        prices.insert("CHF".to_string(), "9.2".parse().unwrap());
+
+        prices
+    }
+
+    #[test]
+    fn price_test() {
+        let rate = Rate::new(Currency::bitcoin(), "usd", build_prices());
+        assert_eq!("USD", rate.quote());
+        assert_eq!("10.05", rate.price().to_string());
+        assert_eq!("9.2", rate.price_in("chf").unwrap().to_string());
+        assert_eq!(None, rate.price_in("JPY"));
+        assert_eq!(10.05, rate.value());
+    }
+
+    #[test]
+    fn from_price_test() {
+        let rate = Rate::from_price(Currency::ripple(), "eur", "2.5".parse().unwrap());
+        assert_eq!("EUR", rate.quote());
+        assert_eq!("2.5", rate.price().to_string());
+        assert_eq!(250.0, rate.value());
+    }
+
+    #[test]
+    fn time_test() {
+        let rate = Rate::new(Currency::bitcoin(), "USD", build_prices());
+        assert_eq!(rate.fetched_at, rate.time());
+
+        let source_time = Utc.with_ymd_and_hms(2017, 12, 3, 19, 5, 0).unwrap();
+        let rate = rate.with_source_time(Some(source_time));
+        assert_eq!(source_time, rate.time());
+        assert_eq!(90, rate.age(source_time + ::chrono::Duration::seconds(90)).num_seconds());
+    }
+
+    #[test]
+    fn with_quote_test() {
+        let source_time = Utc.with_ymd_and_hms(2017, 12, 3, 19, 5, 0).unwrap();
+        let rate = Rate::new(Currency::bitcoin(), "USD", build_prices()).with_source_time(Some(source_time));
+        let rate = rate.with_x(4).with_quote("CHF");
+        assert_eq!(Some(source_time), rate.source_time);
+        assert_eq!("CHF", rate.quote());
+        assert_eq!("9.2", rate.price().to_string());
+        assert_eq!(9.2, rate.value());
+        assert_eq!(4, rate.x());
+    }
+
+    #[test]
+    fn with_price_test() {
+        let source_time = Utc.with_ymd_and_hms(2017, 12, 3, 19, 5, 0).unwrap();
+        let rate = Rate::new(Currency::bitcoin(), "USD", build_prices()).with_source_time(Some(source_time)).with_x(4);
+        let rate = rate.with_price("10.5".parse().unwrap());
+        assert_eq!("10.5", rate.price().to_string());
+        assert_eq!(None, rate.price_in("CHF"));
+        assert_eq!(Some(source_time), rate.source_time);
+        assert_eq!(4, rate.x());
+    }
+
+    #[test]
+    fn price_to_value_relative_test() {
+        let anchor = Price::from_f64(200.0);
+        assert_eq!(0.0, Rate::price_to_value_relative(Price::from_f64(200.0), anchor));
+        assert_eq!(2_500.0, Rate::price_to_value_relative(Price::from_f64(250.0), anchor));
+        assert_eq!(-5_000.0, Rate::price_to_value_relative(Price::from_f64(100.0), anchor));
+        assert_eq!(90_000.0, Rate::price_to_value_relative(Price::from_f64(2_000.0), anchor));
+        assert_eq!(0.0, Rate::price_to_value_relative(Price::from_f64(100.0), Price::zero()));
+    }
+
+    #[test]
+    fn value_test() {
+        // Prices beyond the range of the chart coordinates keep their value
+        let rate = Rate::from_price(Currency::bitcoin(), "USD", Price::from_f64(98_765.43));
+        assert_eq!(98_765.43, rate.value());
+        assert_eq!(1.5, rate.with_value(1.5).value());
+        assert_eq!(3, rate.with_y(3).y());
+    }
+
+    #[test]
+    #[should_panic]
+    fn unplaced_y_test() {
+        Rate::from_price(Currency::bitcoin(), "USD", Price::from_f64(1.0)).y();
+    }
+}
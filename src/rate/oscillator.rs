@@ -0,0 +1,153 @@
+use chart::YRange;
+use super::indicator::ema;
+
+/// Momentum oscillator computed over the prices of a rate series, parsed from `rsi[:PERIOD]` or
+/// `macd[:FAST:SLOW:SIGNAL]`
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Oscillator {
+    /// Relative strength index over the given number of changes
+    Rsi(usize),
+    /// Difference between a fast and a slow exponential moving average and its signal line
+    Macd(usize, usize, usize),
+}
+
+impl Oscillator {
+    pub fn from_str(input: &str) -> Result<Self, ()> {
+        let parts: Vec<&str> = input.split(':').map(str::trim).collect();
+        let mut periods: Vec<usize> = vec![];
+        for part in &parts[1..] {
+            match part.parse::<usize>() {
+                Ok(period) if period > 0 => periods.push(period),
+                _ => return Err(()),
+            }
+        }
+
+        match (parts[0].to_lowercase().as_ref(), periods.as_slice()) {
+            ("rsi", []) => Ok(Oscillator::Rsi(14)),
+            ("rsi", &[period]) => Ok(Oscillator::Rsi(period)),
+            ("macd", []) => Ok(Oscillator::Macd(12, 26, 9)),
+            ("macd", &[fast, slow, signal]) if fast < slow => Ok(Oscillator::Macd(fast, slow, signal)),
+            _ => Err(()),
+        }
+    }
+
+    /// Return the name of the oscillator (e.g. "RSI(14)")
+    pub fn name(&self) -> String {
+        match *self {
+            Oscillator::Rsi(period) => format!("RSI({})", period),
+            Oscillator::Macd(fast, slow, signal) => format!("MACD({},{},{})", fast, slow, signal),
+        }
+    }
+
+    /// Return the lines of the oscillator as values aligned with the prices
+    ///
+    /// The RSI consists of one line, the MACD of the MACD line and its signal line. Values are missing
+    /// until enough prices are known
+    pub fn compute(&self, prices: &[f64]) -> Vec<Vec<Option<f64>>> {
+        match *self {
+            Oscillator::Rsi(period) => vec![rsi(prices, period)],
+            Oscillator::Macd(fast, slow, signal) => {
+                let macd: Vec<Option<f64>> = ema(prices, fast).iter().zip(ema(prices, slow))
+                    .map(|(fast, slow)| Some((*fast)? - slow?))
+                    .collect();
+
+                // The signal line only averages the known MACD values
+                let known: Vec<f64> = macd.iter().filter_map(|value| *value).collect();
+                let mut signal_values = ema(&known, signal).into_iter();
+                let signal_line = macd.iter()
+                    .map(|value| value.and_then(|_| signal_values.next()?))
+                    .collect();
+
+                vec![macd, signal_line]
+            }
+        }
+    }
+
+    /// Return the range of the oscillator's scale
+    ///
+    /// The RSI always spans 0 to 100, while the MACD is centered on zero and fits the values of the lines
+    pub fn range(&self, lines: &[Vec<Option<f64>>]) -> YRange {
+        match *self {
+            Oscillator::Rsi(_) => YRange { min: Some(0.0), max: Some(100.0) },
+            Oscillator::Macd(..) => {
+                let extent = lines.iter().flatten().filter_map(|value| *value).fold(0.0, |extent: f64, value| extent.max(value.abs()));
+                let extent = if extent > 0.0 { extent } else { 1.0 };
+
+                YRange { min: Some(-extent), max: Some(extent) }
+            }
+        }
+    }
+
+    /// Return the values at which threshold lines are drawn
+    pub fn thresholds(&self) -> Vec<f64> {
+        match *self {
+            Oscillator::Rsi(_) => vec![70.0, 30.0],
+            Oscillator::Macd(..) => vec![0.0],
+        }
+    }
+}
+
+/// Return the relative strength index at each position, using Wilder's smoothing of the gains and losses
+pub fn rsi(values: &[f64], period: usize) -> Vec<Option<f64>> {
+    let mut result = vec![None; values.len()];
+    if period == 0 || values.len() <= period {
+        return result;
+    }
+    let changes: Vec<f64> = values.windows(2).map(|pair| pair[1] - pair[0]).collect();
+    let strength = |gain: f64, loss: f64| -> f64 {
+        if loss == 0.0 { 100.0 } else { 100.0 - 100.0 / (1.0 + gain / loss) }
+    };
+
+    let mut gain = changes[..period].iter().map(|change| change.max(0.0)).sum::<f64>() / period as f64;
+    let mut loss = changes[..period].iter().map(|change| (-change).max(0.0)).sum::<f64>() / period as f64;
+    result[period] = Some(strength(gain, loss));
+    for (index, change) in changes.iter().enumerate().skip(period) {
+        gain = (gain * (period - 1) as f64 + change.max(0.0)) / period as f64;
+        loss = (loss * (period - 1) as f64 + (-change).max(0.0)) / period as f64;
+        result[index + 1] = Some(strength(gain, loss));
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_str_test() {
+        assert_eq!(Ok(Oscillator::Rsi(14)), Oscillator::from_str("rsi"));
+        assert_eq!(Ok(Oscillator::Rsi(7)), Oscillator::from_str("RSI:7"));
+        assert_eq!(Ok(Oscillator::Macd(12, 26, 9)), Oscillator::from_str("macd"));
+        assert_eq!(Ok(Oscillator::Macd(5, 10, 3)), Oscillator::from_str("macd:5:10:3"));
+        assert!(Oscillator::from_str("rsi:0").is_err());
+        assert!(Oscillator::from_str("macd:10:5:3").is_err());
+        assert!(Oscillator::from_str("macd:5:10").is_err());
+        assert!(Oscillator::from_str("sma:20").is_err());
+    }
+
+    #[test]
+    fn rsi_test() {
+        // Without losses the RSI is 100, then the averages are smoothed over the period
+        assert_eq!(vec![None, None, Some(100.0), Some(100.0 - 100.0 / 1.5)], rsi(&[1.0, 2.0, 3.0, 1.0], 2));
+        assert_eq!(vec![None, None, Some(50.0)], rsi(&[1.0, 3.0, 1.0], 2));
+        assert_eq!(vec![None, None], rsi(&[1.0, 2.0], 2));
+    }
+
+    #[test]
+    fn macd_test() {
+        let lines = Oscillator::Macd(1, 2, 2).compute(&[1.0, 3.0, 5.0, 4.0]);
+
+        // The fast EMA over one price is the price itself
+        assert_eq!(vec![None, Some(1.0), Some(1.0), Some(0.0)], lines[0]);
+        assert_eq!(vec![None, None, Some(1.0)], lines[1][..3].to_vec());
+        assert!((lines[1][3].unwrap() - 1.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn range_test() {
+        assert_eq!(YRange { min: Some(0.0), max: Some(100.0) }, Oscillator::Rsi(14).range(&[]));
+        assert_eq!(YRange { min: Some(-3.0), max: Some(3.0) }, Oscillator::Macd(12, 26, 9).range(&[vec![None, Some(-3.0), Some(2.0)]]));
+        assert_eq!(YRange { min: Some(-1.0), max: Some(1.0) }, Oscillator::Macd(12, 26, 9).range(&[vec![None]]));
+    }
+}
@@ -0,0 +1,396 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+use std::str::FromStr;
+use serde::{Serialize, Serializer, Deserialize, Deserializer};
+use serde::de::{self, Visitor};
+
+/// Maximum number of decimals a `Price` keeps
+const MAX_SCALE: u32 = 18;
+
+/// Exact decimal number used for prices
+///
+/// The value is stored as `mantissa * 10^-scale`. Provider strings like "248.16084013" are parsed
+/// without going through a float, so no cents are lost at high prices. The value is always kept
+/// normalized (without trailing zeros), which allows the derived equality checks.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Price {
+    mantissa: i128,
+    scale: u32,
+}
+
+impl Price {
+    pub fn new(mantissa: i128, scale: u32) -> Self {
+        let mut price = Price { mantissa, scale };
+        if price.scale > MAX_SCALE {
+            price = price.round_to(MAX_SCALE);
+        }
+
+        price.normalize()
+    }
+
+    pub fn zero() -> Self {
+        Price { mantissa: 0, scale: 0 }
+    }
+
+    /// Build a price from a float using the shortest representation that round-trips
+    ///
+    /// Not-a-number and infinite values result in zero
+    pub fn from_f64(value: f64) -> Self {
+        if !value.is_finite() {
+            return Self::zero();
+        }
+
+        value.to_string().parse().unwrap_or_else(|_| Self::zero())
+    }
+
+    pub fn to_f64(self) -> f64 {
+        self.mantissa as f64 / 10f64.powi(self.scale as i32)
+    }
+
+    #[allow(unused)]
+    pub fn abs(&self) -> Self {
+        Price { mantissa: self.mantissa.abs(), scale: self.scale }
+    }
+
+    /// Round the price to the given number of decimals (half away from zero)
+    pub fn round_to(&self, decimals: u32) -> Self {
+        if decimals >= self.scale {
+            return *self;
+        }
+
+        // A divisor too large for the mantissa type exceeds any mantissa, so the price rounds to zero
+        let divisor = match 10i128.checked_pow(self.scale - decimals) {
+            Some(divisor) => divisor,
+            None => return Self::zero(),
+        };
+        let quotient = self.mantissa / divisor;
+        let remainder = self.mantissa % divisor;
+        let rounded = if remainder.abs() * 2 >= divisor {
+            quotient + self.mantissa.signum()
+        } else {
+            quotient
+        };
+
+        Price { mantissa: rounded, scale: decimals }.normalize()
+    }
+
+    fn normalize(mut self) -> Self {
+        if self.mantissa == 0 {
+            return Self::zero();
+        }
+        while self.scale > 0 && self.mantissa % 10 == 0 {
+            self.mantissa /= 10;
+            self.scale -= 1;
+        }
+
+        self
+    }
+
+    /// Return the mantissa of the price expressed with the given scale
+    fn mantissa_with_scale(&self, scale: u32) -> Option<i128> {
+        debug_assert!(scale >= self.scale);
+        10i128.checked_pow(scale - self.scale).and_then(|factor| self.mantissa.checked_mul(factor))
+    }
+
+    /// Bring both prices to the same scale
+    fn align(&self, other: &Price) -> Option<(i128, i128, u32)> {
+        let scale = self.scale.max(other.scale);
+
+        Some((self.mantissa_with_scale(scale)?, other.mantissa_with_scale(scale)?, scale))
+    }
+}
+
+impl Default for Price {
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+
+impl FromStr for Price {
+    type Err = ();
+
+    fn from_str(input: &str) -> Result<Self, ()> {
+        let input = input.trim();
+        let (number, exponent) = match input.find(['e', 'E']) {
+            Some(position) => (&input[..position], input[position + 1..].parse::<i32>().map_err(|_| ())?),
+            None => (input, 0),
+        };
+        let (negative, number) = match number.chars().next() {
+            Some('-') => (true, &number[1..]),
+            Some('+') => (false, &number[1..]),
+            _ => (false, number),
+        };
+        let (integer_part, fraction_part) = match number.find('.') {
+            Some(position) => (&number[..position], &number[position + 1..]),
+            None => (number, ""),
+        };
+        if integer_part.is_empty() && fraction_part.is_empty() {
+            return Err(());
+        }
+
+        let mut mantissa: i128 = 0;
+        for character in integer_part.chars().chain(fraction_part.chars()) {
+            let digit = character.to_digit(10).ok_or(())?;
+            mantissa = mantissa.checked_mul(10)
+                .and_then(|m| m.checked_add(digit as i128))
+                .ok_or(())?;
+        }
+        if negative {
+            mantissa = -mantissa;
+        }
+
+        let scale = (fraction_part.len() as i32).checked_sub(exponent).ok_or(())?;
+        if scale >= 0 {
+            Ok(Price::new(mantissa, scale as u32))
+        } else {
+            let factor = 10i128.checked_pow((-scale) as u32).ok_or(())?;
+            Ok(Price::new(mantissa.checked_mul(factor).ok_or(())?, 0))
+        }
+    }
+}
+
+impl PartialOrd for Price {
+    fn partial_cmp(&self, other: &Price) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Price {
+    fn cmp(&self, other: &Price) -> Ordering {
+        match self.align(other) {
+            Some((left, right, _)) => left.cmp(&right),
+            None => self.to_f64().partial_cmp(&other.to_f64()).unwrap_or(Ordering::Equal),
+        }
+    }
+}
+
+impl Add for Price {
+    type Output = Price;
+
+    fn add(self, other: Price) -> Price {
+        let (mut left, mut right) = (self, other);
+        loop {
+            let sum = left.align(&right)
+                .and_then(|(left, right, scale)| Some(Price::new(left.checked_add(right)?, scale)));
+            if let Some(sum) = sum {
+                return sum;
+            }
+
+            // Give up the least significant decimal of the more precise summand
+            if left.scale == 0 && right.scale == 0 {
+                // Only summands of the same sign overflow, so the sum saturates in their direction
+                return Price::new(left.mantissa.signum() * i128::MAX, 0);
+            } else if left.scale >= right.scale {
+                left = left.round_to(left.scale - 1);
+            } else {
+                right = right.round_to(right.scale - 1);
+            }
+        }
+    }
+}
+
+impl Sub for Price {
+    type Output = Price;
+
+    fn sub(self, other: Price) -> Price {
+        self + (-other)
+    }
+}
+
+impl Neg for Price {
+    type Output = Price;
+
+    fn neg(self) -> Price {
+        Price { mantissa: -self.mantissa, scale: self.scale }
+    }
+}
+
+impl Mul for Price {
+    type Output = Price;
+
+    fn mul(self, other: Price) -> Price {
+        let (mut left, mut right) = (self, other);
+        loop {
+            if let Some(mantissa) = left.mantissa.checked_mul(right.mantissa) {
+                return Price::new(mantissa, left.scale + right.scale);
+            }
+
+            // Give up the least significant decimal of the more precise factor
+            if left.scale == 0 && right.scale == 0 {
+                return Price::from_f64(self.to_f64() * other.to_f64());
+            } else if left.scale >= right.scale {
+                left = left.round_to(left.scale - 1);
+            } else {
+                right = right.round_to(right.scale - 1);
+            }
+        }
+    }
+}
+
+/// Formats the exact value or, if a precision is given, the value rounded to that many decimals
+impl fmt::Display for Price {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // The decimals missing up to the precision are padded with zeros
+        let (mantissa, scale, padding) = match f.precision() {
+            Some(precision) => {
+                let rounded = self.round_to(precision as u32);
+                (rounded.mantissa, rounded.scale as usize, precision - rounded.scale as usize)
+            }
+            None => (self.mantissa, self.scale as usize, 0),
+        };
+
+        let digits = mantissa.abs().to_string();
+        let digits = if digits.len() <= scale {
+            format!("{}{}", "0".repeat(scale + 1 - digits.len()), digits)
+        } else {
+            digits
+        };
+        let (integer_part, fraction_part) = digits.split_at(digits.len() - scale);
+
+        let output = if scale + padding > 0 {
+            format!("{}.{}{}", integer_part, fraction_part, "0".repeat(padding))
+        } else {
+            integer_part.to_string()
+        };
+        f.pad_integral(mantissa >= 0, "", &output)
+    }
+}
+
+impl Serialize for Price {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+struct PriceVisitor;
+
+impl<'de> Visitor<'de> for PriceVisitor {
+    type Value = Price;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a decimal number or a string containing one")
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Price, E> where E: de::Error {
+        value.parse().map_err(|_| E::custom(format!("invalid price '{}'", value)))
+    }
+
+    fn visit_f64<E>(self, value: f64) -> Result<Price, E> where E: de::Error {
+        Ok(Price::from_f64(value))
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<Price, E> where E: de::Error {
+        Ok(Price::new(value as i128, 0))
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<Price, E> where E: de::Error {
+        Ok(Price::new(value as i128, 0))
+    }
+}
+
+impl<'de> Deserialize<'de> for Price {
+    fn deserialize<D>(deserializer: D) -> Result<Price, D::Error> where D: Deserializer<'de> {
+        deserializer.deserialize_any(PriceVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    fn p(input: &str) -> Price {
+        input.parse().unwrap()
+    }
+
+    #[test]
+    fn from_str_test() {
+        assert_eq!(Price::new(2481608401, 7), p("248.16084013").round_to(7));
+        assert_eq!(Price::new(1923456, 2), p("19234.56"));
+        assert_eq!(Price::new(1923456, 2), p("19234.5600"));
+        assert_eq!(Price::new(5, 7), p("0.0000005"));
+        assert_eq!(Price::new(5, 7), p("5e-7"));
+        assert_eq!(Price::new(-15, 1), p("-1.5"));
+        assert_eq!(Price::new(15, 0), p("1.5E1"));
+        assert_eq!(Price::new(5, 1), p(".5"));
+        assert_eq!(Price::zero(), p("0.000"));
+
+        assert!("".parse::<Price>().is_err());
+        assert!(".".parse::<Price>().is_err());
+        assert!("1,5".parse::<Price>().is_err());
+        assert!("abc".parse::<Price>().is_err());
+    }
+
+    #[test]
+    fn tiny_value_test() {
+        // Values below the smallest decimal kept round to zero instead of overflowing
+        assert_eq!(Price::zero(), p("1e-100"));
+        assert_eq!(Price::zero(), p("-4.2e-60"));
+        assert_eq!(Price::zero(), Price::new(i128::MAX, 60));
+        assert_eq!(Price::new(1, 18), p("5e-19"));
+    }
+
+    #[test]
+    fn display_test() {
+        assert_eq!("19234.56", p("19234.56").to_string());
+        assert_eq!("0.0000005", p("0.0000005").to_string());
+        assert_eq!("-1.5", p("-1.5").to_string());
+        assert_eq!("12", p("12.000").to_string());
+        assert_eq!("0", Price::zero().to_string());
+
+        assert_eq!("19234.56", format!("{:.2}", p("19234.5625")));
+        assert_eq!("19234.57", format!("{:.2}", p("19234.565")));
+        assert_eq!("19235", format!("{:.0}", p("19234.5")));
+        assert_eq!("2.2000", format!("{:.4}", p("2.2")));
+        assert_eq!("0.00", format!("{:.2}", p("0.0000005")));
+        assert_eq!("-1.50", format!("{:.2}", p("-1.5")));
+        assert_eq!("   1.50", format!("{:>7.2}", p("1.5")));
+        // Precisions beyond the range of the mantissa are padded with zeros
+        assert_eq!(format!("1.5{}", "0".repeat(39)), format!("{:.40}", p("1.5")));
+        assert_eq!(format!("-2.{}", "0".repeat(40)), format!("{:.40}", p("-2")));
+    }
+
+    #[test]
+    fn from_f64_test() {
+        assert_eq!(p("478.68"), Price::from_f64(478.68));
+        assert_eq!(p("0.03652"), Price::from_f64(0.03652));
+        assert_eq!(Price::zero(), Price::from_f64(f64::NAN));
+    }
+
+    #[test]
+    fn arithmetic_test() {
+        assert_eq!(p("19234.57"), p("19234.56") + p("0.01"));
+        assert_eq!(p("-0.01"), p("19234.56") - p("19234.57"));
+        assert_eq!(p("38469.12"), p("19234.56") * p("2"));
+        assert_eq!(p("0.00000001"), p("0.0001") * p("0.0001"));
+        assert_eq!(p("1e-18"), p("1e-10") * p("1e-8"));
+        assert_eq!(Price::zero(), p("1e-10") * p("1e-10"));
+    }
+
+    #[test]
+    fn add_overflow_test() {
+        // Sums beyond the range of the mantissa give up decimals first and saturate without any left
+        assert_eq!(Price::new(17014118346046923173168730371588410582, 1), Price::new(i128::MAX - 5, 2) + p("1"));
+        assert_eq!(Price::new(i128::MAX, 0), Price::new(i128::MAX, 0) + p("1"));
+        assert_eq!(Price::new(-i128::MAX, 0), Price::new(-i128::MAX, 0) - p("2"));
+        assert_eq!(p("1"), Price::new(i128::MAX, 0) + Price::new(1 - i128::MAX, 0));
+    }
+
+    #[test]
+    fn ord_test() {
+        assert!(p("19234.56") < p("19234.57"));
+        assert!(p("-1") < p("0.0001"));
+        assert!(p("2") > p("1.99999999"));
+        assert_eq!(Ordering::Equal, p("1.50").cmp(&p("1.5")));
+    }
+
+    #[test]
+    fn serde_test() {
+        assert_eq!("\"19234.56\"", serde_json::to_string(&p("19234.56")).unwrap());
+        assert_eq!(p("19234.56"), serde_json::from_str::<Price>("\"19234.56\"").unwrap());
+        assert_eq!(p("478.68"), serde_json::from_str::<Price>("478.68").unwrap());
+        assert_eq!(p("1515878702"), serde_json::from_str::<Price>("1515878702").unwrap());
+        assert!(serde_json::from_str::<Price>("\"abc\"").is_err());
+    }
+}
@@ -1,9 +1,13 @@
+use super::Candle;
 use super::Rate;
 
 #[derive(Debug)]
 pub struct RateSeries {
     max_len: usize,
     data: Vec<Rate>,
+    candle_width: Option<i64>,
+    max_candles: usize,
+    candles: Vec<Candle>,
 }
 
 impl RateSeries {
@@ -11,24 +15,85 @@ impl RateSeries {
         RateSeries {
             max_len,
             data: Vec::with_capacity(max_len),
+            candle_width: None,
+            max_candles: 0,
+            candles: vec![],
         }
     }
+
+    /// Build a series which additionally aggregates the rates into candles of `candle_width` seconds
+    ///
+    /// The candles are kept independently from the raw rates, so they can cover a longer time span
+    pub fn with_candles(max_len: usize, candle_width: i64, max_candles: usize) -> Self {
+        assert!(candle_width > 0, "Candle width must be bigger than zero");
+        RateSeries {
+            max_len,
+            data: Vec::with_capacity(max_len),
+            candle_width: Some(candle_width),
+            max_candles,
+            candles: Vec::with_capacity(max_candles),
+        }
+    }
+
     pub fn push(&mut self, rate: Rate) {
+        self.push_candle(&rate);
         if self.data.len() == self.max_len {
             self.data.remove(0);
         }
         self.data.push(rate);
     }
 
+    fn push_candle(&mut self, rate: &Rate) {
+        let candle_width = match self.candle_width {
+            Some(candle_width) => candle_width,
+            None => return,
+        };
+
+        // Late rates are added to their bucket, if it is still kept
+        if let Some(candle) = self.candles.iter_mut().rev().find(|candle| candle.contains(rate)) {
+            candle.update(rate);
+            return;
+        }
+        let bucket_start = Candle::bucket_start(rate, candle_width);
+        if let Some(first) = self.candles.first() {
+            if bucket_start < first.start.timestamp() {
+                return;
+            }
+        }
+
+        let position = self.candles.iter()
+            .position(|candle| candle.start.timestamp() > bucket_start)
+            .unwrap_or(self.candles.len());
+        self.candles.insert(position, Candle::new(rate, candle_width));
+        if self.candles.len() > self.max_candles {
+            self.candles.remove(0);
+        }
+    }
+
     pub fn data(&self) -> &Vec<Rate> {
         &self.data
     }
 
+    /// Return the maximum number of raw rates kept
+    pub fn max_len(&self) -> usize {
+        self.max_len
+    }
+
     #[allow(unused)]
     pub fn len(&self) -> usize {
         self.data.len()
     }
 
+    /// Return the candles aggregated from the pushed rates (empty if aggregation is disabled)
+    pub fn candles(&self) -> &Vec<Candle> {
+        &self.candles
+    }
+
+    /// Return the duration of a candle in seconds, if candles are aggregated
+    pub fn candle_width(&self) -> Option<i64> {
+        self.candle_width
+    }
+
     pub fn last(&self) -> Option<&Rate> {
         self.data.last()
     }
@@ -42,16 +107,66 @@ impl RateSeries {
 mod tests {
     use super::*;
     use rate::Currency;
+    use rate::Price;
+    use chrono::prelude::*;
 
     #[test]
     fn push_test() {
         let mut rs = RateSeries::new(2);
-        rs.push(Rate::new(Currency::Bitcoin, 0.01, 0.02));
-        rs.push(Rate::new(Currency::Bitcoin, 0.11, 0.12));
+        rs.push(Rate::from_price(Currency::bitcoin(), "USD", Price::from_f64(0.01)));
+        rs.push(Rate::from_price(Currency::bitcoin(), "USD", Price::from_f64(0.11)));
+
+        assert_eq!(2, rs.len());
 
+        rs.push(Rate::from_price(Currency::bitcoin(), "USD", Price::from_f64(0.21)));
         assert_eq!(2, rs.len());
+        assert!(rs.candles().is_empty());
+    }
+
+    fn build_rate(timestamp: i64, price: &str) -> Rate {
+        Rate::from_price(Currency::bitcoin(), "USD", price.parse().unwrap())
+            .with_source_time(Utc.timestamp_opt(timestamp, 0).single())
+    }
 
-        rs.push(Rate::new(Currency::Bitcoin, 0.21, 0.22));
+    #[test]
+    fn candles_test() {
+        let mut rs = RateSeries::with_candles(2, 60, 2);
+        rs.push(build_rate(1_200, "10"));
+        rs.push(build_rate(1_230, "12"));
+        rs.push(build_rate(1_250, "11"));
+        rs.push(build_rate(1_260, "9"));
+
+        // The raw view is capped while the candles still cover all rates
         assert_eq!(2, rs.len());
+        assert_eq!(2, rs.candles().len());
+
+        let first = &rs.candles()[0];
+        assert_eq!(1_200, first.start.timestamp());
+        assert_eq!(("10", "12", "10", "11"), (
+            first.open.to_string().as_str(),
+            first.high.to_string().as_str(),
+            first.low.to_string().as_str(),
+            first.close.to_string().as_str(),
+        ));
+        assert_eq!(3, first.ticks);
+        assert_eq!(1_260, rs.candles()[1].start.timestamp());
+
+        rs.push(build_rate(1_320, "8"));
+        assert_eq!(2, rs.candles().len());
+        assert_eq!(1_260, rs.candles()[0].start.timestamp());
+    }
+
+    #[test]
+    fn candles_late_rate_test() {
+        let mut rs = RateSeries::with_candles(10, 60, 3);
+        rs.push(build_rate(1_200, "10"));
+        rs.push(build_rate(1_380, "12"));
+        rs.push(build_rate(1_300, "11"));
+        rs.push(build_rate(1_210, "14"));
+        rs.push(build_rate(1_100, "1"));
+
+        let starts: Vec<i64> = rs.candles().iter().map(|c| c.start.timestamp()).collect();
+        assert_eq!(vec![1_200, 1_260, 1_380], starts);
+        assert_eq!("14", rs.candles()[0].high.to_string());
     }
 }
@@ -0,0 +1,23 @@
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug)]
+pub struct RegistryError {
+    message: String,
+}
+
+impl RegistryError {
+    pub fn new<S>(message: S) -> Self
+        where S: Into<String> {
+        RegistryError { message: message.into() }
+    }
+}
+
+
+impl fmt::Display for RegistryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl Error for RegistryError {}
@@ -0,0 +1,219 @@
+use chart::Mode;
+use rate::Currency;
+use ui::CoordinatePrecision;
+use ui::Error;
+use ui::Screen;
+use ui::Size;
+use ui::layout;
+use ui::layout::Pane;
+use ui::medium::*;
+use util;
+use super::RatePrinter;
+
+/// Settings of a single pane of the dashboard, parsed from `CURRENCY[:PROVIDER[:MODE[:WIDTHxHEIGHT]]]`
+///
+/// Empty parts fall back to the global settings (e.g. "eth::log" uses the default provider)
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaneSpec {
+    pub currency: String,
+    pub provider: Option<String>,
+    pub mode: Option<Mode>,
+    pub width: Option<CoordinatePrecision>,
+    pub height: Option<CoordinatePrecision>,
+}
+
+impl PaneSpec {
+    pub fn from_str(input: &str) -> Result<Self, ()> {
+        let parts: Vec<&str> = input.split(':').map(str::trim).collect();
+        if parts.len() > 4 || parts[0].is_empty() {
+            return Err(());
+        }
+        let part = |index: usize| parts.get(index).cloned().filter(|part| !part.is_empty());
+
+        let mode = match part(2) {
+            Some(mode) => Some(Mode::from_str(mode)?),
+            None => None,
+        };
+        let (width, height) = match part(3) {
+            Some(size) => {
+                let mut dimensions = size.splitn(2, 'x').map(|dimension| dimension.parse::<CoordinatePrecision>());
+                match (dimensions.next(), dimensions.next()) {
+                    (Some(Ok(width)), Some(Ok(height))) if width > 0 && height > 0 => (Some(width), Some(height)),
+                    _ => return Err(()),
+                }
+            }
+            None => (None, None),
+        };
+
+        Ok(PaneSpec {
+            currency: parts[0].to_string(),
+            provider: part(1).map(str::to_string),
+            mode,
+            width,
+            height,
+        })
+    }
+}
+
+/// Minimum width of a pane, leaving room for the scalas
+const MIN_PANE_WIDTH: CoordinatePrecision = 11;
+
+/// Minimum height of a pane, leaving room for the scala and one line for the footer
+const MIN_PANE_HEIGHT: CoordinatePrecision = 5;
+
+/// View drawn into a region of the dashboard
+struct DashboardPane<'a> {
+    pane: Pane,
+    /// Size given in the pane's spec, which the region is shrunk to
+    width: Option<CoordinatePrecision>,
+    height: Option<CoordinatePrecision>,
+    printer: RatePrinter<'a>,
+    currency: Currency,
+}
+
+/// Several views drawn side by side, each into its own region of the screen
+pub struct Dashboard<'a> {
+    panes: Vec<DashboardPane<'a>>,
+    /// Number of panes per row
+    columns: usize,
+    screen: Screen<Terminal>,
+}
+
+impl<'a> Dashboard<'a> {
+    pub fn new(screen: Screen<Terminal>, columns: usize) -> Self {
+        Dashboard { panes: vec![], columns, screen }
+    }
+
+    /// Return the area of the screen the grid of panes is laid out in
+    ///
+    /// The last line is left empty like in the full-screen view
+    pub fn get_area(screen_size: Size) -> Size {
+        Size::new(screen_size.width, screen_size.height.saturating_sub(1).max(1))
+    }
+
+    /// Return if a chart and its footer fit into the pane
+    pub fn fits(pane: &Pane) -> bool {
+        pane.size.width >= MIN_PANE_WIDTH && pane.size.height >= MIN_PANE_HEIGHT
+    }
+
+    /// Draw the rates of the currency into the pane
+    ///
+    /// The printer's chart should fit into the pane, leaving one line for the footer. Anything
+    /// exceeding the pane is cut off. If the terminal is resized, the pane is shrunk to the size of the
+    /// spec again
+    pub fn add_pane(&mut self, pane: Pane, spec: &PaneSpec, printer: RatePrinter<'a>, currency: Currency) {
+        self.panes.push(DashboardPane { pane, width: spec.width, height: spec.height, printer, currency });
+    }
+
+    /// Return the printers of all panes
+    pub fn printers_mut(&mut self) -> Vec<&mut RatePrinter<'a>> {
+        self.panes.iter_mut().map(|entry| &mut entry.printer).collect()
+    }
+
+    /// Fetch the current rates of all panes and draw them
+    ///
+    /// If the rates of a pane can not be fetched, the error is drawn into the pane and the other panes
+    /// are still drawn
+    pub fn get_and_print_rates(&mut self) -> Result<(), Error> {
+        self.update_layout()?;
+        for entry in &mut self.panes {
+            let output = if !Self::fits(&entry.pane) {
+                draw_message("Pane too small", entry.pane.size)
+            } else {
+                match entry.printer.get_and_draw_rates(&entry.currency) {
+                    Ok(output) => output,
+                    Err(error) => draw_message(&format!("{}: {}", entry.currency.symbol(), error), entry.pane.size),
+                }
+            };
+            self.screen.draw_multi_line_text_clipped(&entry.pane.origin, &output, entry.pane.size)?;
+        }
+
+        self.screen.flush()
+    }
+
+    /// Lay the panes out again if the terminal was resized since the last run
+    fn update_layout(&mut self) -> Result<(), Error> {
+        let size = Size::auto()?;
+        if self.screen.size() == size {
+            return Ok(());
+        }
+        self.screen = Screen::default()?;
+
+        let panes = layout::grid(Self::get_area(size), self.panes.len(), self.columns)?;
+        for (entry, pane) in self.panes.iter_mut().zip(panes) {
+            entry.pane = pane.shrink(entry.width, entry.height);
+            entry.printer.resize(entry.pane.size.width, entry.pane.size.height.saturating_sub(1));
+        }
+
+        Ok(())
+    }
+}
+
+/// Draw the message wrapped to the width of the region and padded with spaces to fill it
+fn draw_message(message: &str, size: Size) -> String {
+    let width = (size.width as usize).max(1);
+    let characters: Vec<char> = message.chars().collect();
+    let mut lines: Vec<String> = characters.chunks(width).map(|chunk| chunk.iter().collect()).collect();
+    lines.resize(size.height as usize, String::new());
+
+    lines.iter()
+        .map(|line| format!("{}\n", util::str_pad(line, width, ' ')))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_str_test() {
+        assert_eq!(
+            PaneSpec { currency: "btc".to_string(), provider: None, mode: None, width: None, height: None },
+            PaneSpec::from_str("btc").unwrap()
+        );
+        assert_eq!(
+            PaneSpec { currency: "eth".to_string(), provider: None, mode: Some(Mode::Log), width: None, height: None },
+            PaneSpec::from_str("eth::log").unwrap()
+        );
+        assert_eq!(
+            PaneSpec {
+                currency: "ltc".to_string(),
+                provider: Some("faker".to_string()),
+                mode: Some(Mode::Scale),
+                width: Some(60),
+                height: Some(12),
+            },
+            PaneSpec::from_str("ltc:faker:scale:60x12").unwrap()
+        );
+    }
+
+    #[test]
+    fn from_str_invalid_test() {
+        assert!(PaneSpec::from_str("").is_err());
+        assert!(PaneSpec::from_str(":faker").is_err());
+        assert!(PaneSpec::from_str("btc::invalid").is_err());
+        assert!(PaneSpec::from_str("btc:::60").is_err());
+        assert!(PaneSpec::from_str("btc:::0x12").is_err());
+        assert!(PaneSpec::from_str("btc:faker:log:60x12:extra").is_err());
+    }
+
+    #[test]
+    fn draw_message_test() {
+        assert_eq!("No rat\ne     \n      \n", draw_message("No rate", Size::new(6, 3)));
+        assert_eq!("No\n", draw_message("No rate", Size::new(2, 1)));
+    }
+
+    #[test]
+    fn fits_test() {
+        assert!(Dashboard::fits(&Pane::new(::point::Point::new(0, 0), Size::new(11, 5))));
+        assert!(!Dashboard::fits(&Pane::new(::point::Point::new(0, 0), Size::new(10, 5))));
+        assert!(!Dashboard::fits(&Pane::new(::point::Point::new(0, 0), Size::new(11, 4))));
+    }
+
+    #[test]
+    fn get_area_test() {
+        assert_eq!(Size::new(80, 23), Dashboard::get_area(Size::new(80, 24)));
+        // Terminals reporting no rows still leave one line
+        assert_eq!(Size::new(80, 1), Dashboard::get_area(Size { width: 80, height: 0 }));
+    }
+}
@@ -2,6 +2,7 @@ use chrono::prelude::*;
 use util;
 use rate;
 use rate::RateSeries;
+use rate::PriceTrait;
 use chart::*;
 use term_style::style as color;
 use rate_provider;
@@ -15,112 +16,683 @@ use ui::PixelSequence;
 use ui::medium::*;
 use point::Point;
 
+mod dashboard;
+mod oscillator;
+mod panel;
+mod reference;
 mod trend;
+mod volume;
+mod x_scala;
+mod y_scala;
+
+pub use self::dashboard::Dashboard;
+pub use self::dashboard::PaneSpec;
+pub use self::reference::Reference;
+
+/// Colors of the series if currencies are compared
+const SERIES_COLORS: [fn(&str) -> String; 8] = [
+    color::cyan, color::magenta, color::yellow, color::light_blue,
+    color::light_cyan, color::light_yellow, color::blue, color::light_gray,
+];
+
+/// Symbol in front of each currency in the legend
+const LEGEND_SYMBOL: &str = "\u{25a0}";
+
+/// Point of a series holding its trend
+type SeriesPoint = trend::TrendCell<rate::Rate>;
 
 pub struct RatePrinter<'a> {
-    value: Option<f32>,
+    value: Option<rate::Price>,
     fill: &'a str,
     space: &'a str,
+    area_fill: &'a str,
     provider: &'a str,
+    quote: &'a str,
     time_series: rate::RateSeries,
+    /// Currencies overlaid on the chart and their rates
+    compared: Vec<(rate::Currency, rate::RateSeries)>,
+    /// Indices of the compared currencies whose rate could not be fetched in the latest run
+    failed_compared: Vec<usize>,
+    /// Range of the y-axis in the units of its labels (prices or percent)
+    y_range: YRange,
+    /// Draw the trading volume in a histogram below the chart
+    show_volume: bool,
+    /// Guide lines drawn across the chart
+    references: Vec<Reference>,
+    /// Indicators drawn as extra series over the currency's rates
+    indicators: Vec<rate::Indicator>,
+    /// Oscillator drawn in a panel below the chart
+    oscillator: Option<rate::Oscillator>,
     chart: Chart,
     run_number: usize,
-    screen: Screen<Terminal>,
+    /// Screen of the full-screen view (see `get_and_print_rates()`)
+    screen: Option<Screen<Terminal>>,
 }
 
 impl<'a> RatePrinter<'a> {
-    pub fn new(chart: Chart, value: Option<f32>, provider: &'a str, fill: &'a str, space: &'a str, history_size: Option<usize>) -> Result<Self, Error> {
+    pub fn new(chart: Chart, value: Option<rate::Price>, provider: &'a str, quote: &'a str, fill: &'a str, space: &'a str, history_size: Option<usize>) -> Result<Self, Error> {
         let time_series = build_time_series(&chart, history_size);
-        let screen = Screen::default()?;
         Ok(RatePrinter {
             space,
             fill,
+            area_fill: area::AREA_FILL,
             value,
             provider,
+            quote,
             chart,
             time_series,
-            screen,
+            compared: vec![],
+            failed_compared: vec![],
+            y_range: YRange::auto(),
+            show_volume: false,
+            references: vec![],
+            indicators: vec![],
+            oscillator: None,
+            screen: None,
             run_number: 0,
         })
     }
 
-    pub fn get_and_print_rates(&mut self, currency: rate::Currency) -> Result<rate::Rate, Error> {
+    /// Aggregate the rates into candles of `candle_width` seconds and chart the candles instead of the raw rates
+    ///
+    /// One candle is kept per chart column
+    pub fn enable_candles(&mut self, candle_width: i64) {
+        let max_candles = self.chart.point_columns();
+        self.time_series = RateSeries::with_candles(self.time_series.max_len(), candle_width, max_candles);
+    }
+
+    /// Set the glyph used to fill the area below the points in the area style
+    pub fn set_area_fill(&mut self, area_fill: &'a str) {
+        self.area_fill = area_fill;
+    }
+
+    /// Overlay the rates of the currency on the chart
+    ///
+    /// All series are plotted as the change in percent from their first rate
+    pub fn compare_with(&mut self, currency: rate::Currency) {
+        let time_series = RateSeries::new(self.time_series.max_len());
+        self.compared.push((currency, time_series));
+    }
+
+    /// Draw the trading volume reported by the provider in a histogram below the chart
+    ///
+    /// The histogram is left out if the provider reports no volume or if currencies are compared
+    pub fn enable_volume(&mut self) {
+        self.show_volume = true;
+    }
+
+    /// Draw a horizontal guide line at the reference across the chart
+    pub fn add_reference(&mut self, reference: Reference) {
+        self.references.push(reference);
+    }
+
+    /// Draw the lines of the indicator over the currency's rates, in their own color
+    ///
+    /// Like compared currencies, indicators are drawn over the raw rates instead of candles
+    pub fn add_indicator(&mut self, indicator: rate::Indicator) {
+        self.indicators.push(indicator);
+    }
+
+    /// Draw the lines of the oscillator over the currency's rates in a panel below the chart
+    pub fn set_oscillator(&mut self, oscillator: rate::Oscillator) {
+        self.oscillator = Some(oscillator);
+    }
+
+    /// Set the size of the chart (e.g. after the terminal was resized)
+    pub fn resize(&mut self, width: CoordinatePrecision, height: CoordinatePrecision) {
+        self.chart.set_size(width, height);
+    }
+
+    /// Set the range of the y-axis in the units of its labels (prices, or percent for the percent change)
+    pub fn set_y_range(&mut self, y_range: YRange) {
+        self.y_range = y_range;
+    }
+
+    /// Fix the y-axis to the range currently visible, so it stays the same between redraws
+    pub fn fix_y_range(&mut self) {
+        self.zoom_y_range(1.0);
+    }
+
+    /// Fit the y-axis to the data again
+    pub fn reset_y_range(&mut self) {
+        self.y_range = YRange::auto();
+    }
+
+    /// Fix the y-axis to the range currently visible scaled by `factor` around its center
+    pub fn zoom_y_range(&mut self, factor: f64) {
+        if let Some((min, max)) = self.get_data_range() {
+            self.y_range = self.y_range.zoom(min, max, factor);
+        }
+    }
+
+    /// Fetch the current rate and draw the view full-screen
+    pub fn get_and_print_rates(&mut self, currency: &rate::Currency) -> Result<rate::Rate, Error> {
+        let output = self.get_and_draw_rates(currency)?;
+        let screen = self.get_screen()?;
+        screen.draw_multi_line_text(&Point::new(0, 0), &output)?;
+        screen.flush()?;
+
+        match self.time_series.last() {
+            Some(rate) => Ok(rate.clone()),
+            None => Err(ui_error!(Misc, "No rate fetched")),
+        }
+    }
+
+    /// Fetch the current rate and return the view (chart and footer) sized to the chart
+    pub fn get_and_draw_rates(&mut self, currency: &rate::Currency) -> Result<String, Error> {
         self.run_number += 1;
-        match rate_provider::get(self.provider, currency) {
+        match rate_provider::get(self.provider, currency, self.quote) {
             Ok(rate) => {
                 let last_rate = self.time_series.last().cloned();
                 self.time_series.push(rate.clone());
+                // A compared currency which can not be fetched is skipped in this run and marked in the legend
+                let (provider, quote) = (self.provider, self.quote);
+                self.failed_compared = self.compared.iter_mut().enumerate()
+                    .filter_map(|(index, (currency, time_series))| match rate_provider::get(provider, currency, quote) {
+                        Ok(compared_rate) => {
+                            time_series.push(compared_rate);
+                            None
+                        }
+                        Err(_) => Some(index),
+                    })
+                    .collect();
+                self.chart.y_range = self.get_value_range(&rate.currency);
+                self.chart.sub_chart_heights = self.get_panels().iter().map(|&(_, height)| height).collect();
+                self.chart.y_scala_width = self.get_y_scala_width();
 
-                let output = format!(
+                Ok(format!(
                     "{}{}{}",
                     self.get_header(&rate, &last_rate),
-                    self.get_chart(&rate, &last_rate),
+                    self.get_chart(),
                     self.get_footer(&rate, &last_rate),
-                );
+                ))
+            }
+            Err(e) => Err(ui_error!(Misc, "{}", e.to_string()))
+        }
+    }
+
+    fn draw_row(&self, y_scala: &Option<y_scala::YScala>, row_number: CoordinatePrecision) -> String {
+        match *y_scala {
+            Some(ref y_scala) => y_scala.draw_row(row_number, self.chart.y_scala_width as usize),
+            None => util::str_left_pad("|", self.chart.y_scala_width as usize, ' ').to_string(),
+        }
+    }
+
+    /// Build the y-axis scala for the points in the chart's current mode
+    fn get_y_scala<P: matrix::PointTrait + PriceTrait>(&self, points: &[P]) -> Option<y_scala::YScala> {
+        let currency = points.first()?.currency();
+        let scale_y = self.chart.scale_y(points);
+        let resolution = self.chart.resolution().1;
+
+        Some(match self.get_anchor() {
+            Some(_) => y_scala::YScala::percent(scale_y, resolution),
+            None => y_scala::YScala::new(currency, scale_y, resolution),
+        })
+    }
+
+    /// Return the width of the y-axis scala fitting the labels for all prices in the time series and
+    /// the bounds of the y-range
+    fn get_y_scala_width(&self) -> CoordinatePrecision {
+        let width = self.get_series().iter()
+            .map(|&(anchor, data)| match anchor {
+                Some(anchor) => y_scala::get_percent_width(data, anchor),
+                None => y_scala::get_width(data),
+            })
+            .max()
+            .unwrap_or(0);
+        // The lines of the indicators are placed on the y-axis already, so their prices are labeled
+        let width = self.get_indicator_series().iter()
+            .map(|(_, line)| match self.get_anchor() {
+                Some(anchor) => y_scala::get_percent_width(line, anchor),
+                None => y_scala::get_width(line),
+            })
+            .fold(width, usize::max);
+        let width = match self.time_series.last() {
+            Some(rate) => y_scala::get_range_width(&self.y_range, rate.currency.precision(), self.get_anchor().is_some()).max(width),
+            None => width,
+        };
 
-                let screen = self.get_screen();
-                screen.draw_multi_line_text(&Point::new(0, 0), &output)?;
-                screen.flush()?;
+        let width = match self.get_max_volume() {
+            Some(max_volume) if self.show_volume => volume::label_width(max_volume).max(width),
+            _ => width,
+        };
+        // The oscillator's panel labels the ends of its own scale
+        let width = match (self.oscillator, self.time_series.last()) {
+            (Some(oscillator), Some(rate)) => {
+                let prices: Vec<f64> = self.time_series.data().iter().map(|rate| rate.price().to_f64()).collect();
+                let y_range = oscillator.range(&oscillator.compute(&prices));
 
-                Ok(rate)
+                self::oscillator::label_width(oscillator, &y_range, rate.currency.precision()).max(width)
             }
-            Err(e) => Err(ui_error!(Misc, "{}", e.to_string()))
+            _ => width,
+        };
+
+        width.min(self.chart.width() as usize / 2) as CoordinatePrecision
+    }
+
+    /// Return the panels fitting below the chart, from top to bottom, each with its number of rows
+    ///
+    /// The volume histogram is left out if there is no volume or if currencies are compared
+    fn get_panels(&self) -> Vec<(panel::Panel, CoordinatePrecision)> {
+        let mut panels = vec![];
+        if self.show_volume && self.compared.is_empty() && self.get_max_volume().is_some() {
+            panels.push(panel::Panel::Volume);
+        }
+        if let Some(oscillator) = self.oscillator {
+            panels.push(panel::Panel::Oscillator(oscillator));
+        }
+
+        panel::layout(&panels, self.chart.height().saturating_sub(self.chart.x_scala_height))
+    }
+
+    /// Return the highest volume in the time series, if the provider reports any
+    fn get_max_volume(&self) -> Option<f64> {
+        self.time_series.data().iter()
+            .filter_map(|rate| rate.volume)
+            .fold(None, |max: Option<f64>, v| Some(max.map_or(v, |max| max.max(v))))
+    }
+
+    /// Return the y-range converted into the points' values (see `PointTrait::value()`)
+    fn get_value_range(&self, currency: &rate::Currency) -> YRange {
+        let values_per_unit = match self.get_anchor() {
+            Some(_) => rate::RELATIVE_SCALE / 100.0,
+            None => f64::from(currency.scale()),
+        };
+
+        self.y_range.map(|bound| bound * values_per_unit)
+    }
+
+    /// Return the lowest and highest rate in the time series in the units of the y-axis labels
+    fn get_data_range(&self) -> Option<(f64, f64)> {
+        let values: Vec<f64> = self.get_series().iter()
+            .flat_map(|&(anchor, data)| data.iter().map(move |rate| match anchor {
+                Some(anchor) => y_scala::percent_change(rate.price(), anchor),
+                None => rate.price().to_f64(),
+            }))
+            .collect();
+        let min = values.iter().cloned().fold(None, |min: Option<f64>, v| Some(min.map_or(v, |min| min.min(v))))?;
+        let max = values.iter().cloned().fold(None, |max: Option<f64>, v| Some(max.map_or(v, |max| max.max(v))))?;
+
+        Some((min, max))
+    }
+
+    /// Return the marker if the value lies outside of the y-range, or else the glyph
+    fn mark_out_of_range<'b>(&self, value: f64, glyph: &'b str) -> &'b str {
+        self.chart.y_range.marker(value).unwrap_or(glyph)
+    }
+
+    /// Return the price the changes are relative to, if the y-axis shows the percent change
+    ///
+    /// Without a price given by the user, the first rate in the time series is the anchor
+    fn get_anchor(&self) -> Option<rate::Price> {
+        let anchor = match self.chart.y_axis {
+            YAxis::Price => return None,
+            YAxis::PercentChange(Some(anchor)) => rate::Price::from_f64(anchor),
+            YAxis::PercentChange(None) => self.time_series.data().first()?.price(),
+        };
+
+        if anchor.to_f64() > 0.0 { Some(anchor) } else { None }
+    }
+
+    /// Return the rates of the currency and of the compared ones, each with the price its changes are
+    /// relative to
+    ///
+    /// Compared rates are always relative to their first rate
+    fn get_series(&self) -> Vec<(Option<rate::Price>, &[rate::Rate])> {
+        let mut series: Vec<(Option<rate::Price>, &[rate::Rate])> = vec![(self.get_anchor(), self.time_series.data())];
+        for (_, time_series) in &self.compared {
+            let anchor = time_series.data().first().map(|rate| rate.price()).filter(|anchor| anchor.to_f64() > 0.0);
+            series.push((anchor, time_series.data()));
         }
+
+        series
     }
 
-    fn draw_row(&self, row: Option<&matrix::Row<rate::Rate>>, row_number: CoordinatePrecision) -> String {
-        let header = match row {
-            Some(row) => {
-                let (_, rate) = row.iter().next().expect(&format!("No items found in row at {}", row_number));
+    /// Build the lines of the references placed like the points on the chart's y-axis
+    fn get_reference_lines(&self) -> Vec<configuration::ReferenceLine> {
+        let rates = self.time_series.data();
+        let currency = match rates.first() {
+            Some(rate) => &rate.currency,
+            None => return vec![],
+        };
+        let anchor = self.get_anchor();
+
+        self.references.iter()
+            .filter_map(|reference| {
+                let price = reference.price(rates)?;
+                let value = match anchor {
+                    Some(anchor) => rate::Rate::price_to_value_relative(price, anchor),
+                    None => price.to_f64() * f64::from(currency.scale()),
+                };
 
-                if rate.currency.scale() > 1.0 {
-                    format!("{:.*} |", 2, rate.price_usd)
+                Some(reference.build_line(value, price, currency.precision()))
+            })
+            .collect()
+    }
+
+    /// Return the lines of the indicators over the currency's rates, each with the index of its color
+    ///
+    /// All lines of an indicator (e.g. the Bollinger bands) share its color
+    fn get_indicator_series(&self) -> Vec<(usize, Vec<rate::Rate>)> {
+        if self.indicators.is_empty() {
+            return vec![];
+        }
+        let points = build_points_from_time_series(self.time_series.data(), self.chart.x_axis);
+        let anchor = self.get_anchor();
+
+        self.indicators.iter().enumerate()
+            .flat_map(|(index, indicator)| {
+                let color = self.get_indicator_color(index);
+                indicator.apply(&points).into_iter().map(move |line| (color, place_relative_to(line, anchor)))
+            })
+            .collect()
+    }
+
+    /// Return the index of the color of the indicator with the given index
+    ///
+    /// The colors follow the ones of the compared currencies, but never wrap around to the currency's color
+    fn get_indicator_color(&self, index: usize) -> usize {
+        1 + (self.compared.len() + index) % (SERIES_COLORS.len() - 1)
+    }
+
+    /// Place the points vertically according to the chart's y-axis
+    fn place_on_y_axis<P: matrix::PointTrait + PriceTrait>(&self, points: Vec<P>) -> Vec<P> {
+        place_relative_to(points, self.get_anchor())
+    }
+
+    fn draw_x_scala<P: PriceTrait>(&self, columns: &[Option<P>], height: CoordinatePrecision) -> String {
+        x_scala::draw_x_scala(columns, height as usize, self.chart.y_scala_width as usize)
+    }
+
+    fn get_chart(&self) -> String {
+        if !self.compared.is_empty() || !self.indicators.is_empty() {
+            let points = build_points_from_time_series(self.time_series.data(), self.chart.x_axis);
+
+            return self.draw_series_chart() + &self.draw_panels(points);
+        }
+        match self.time_series.candle_width() {
+            Some(_) => {
+                let candles = build_points_from_time_series(self.time_series.candles(), self.chart.x_axis);
+                let chart = if self.chart.style == Style::Candlestick {
+                    self.draw_candlestick_chart(candles.clone())
                 } else {
-                    format!("{} |", rate.price_usd.round())
-                }
+                    self.draw_chart(candles.clone())
+                };
+
+                chart + &self.draw_panels(candles)
             }
-            None => "|".to_string(),
-        };
+            None => {
+                let points = build_points_from_time_series(self.time_series.data(), self.chart.x_axis);
+
+                self.draw_chart(points.clone()) + &self.draw_panels(points)
+            }
+        }
+    }
+
+    /// Draw the panels below the chart for the points drawn above
+    fn draw_panels<P>(&self, points: Vec<P>) -> String
+        where P: matrix::PointTrait + PriceTrait {
+        self.get_panels().iter().enumerate()
+            .map(|(index, &(panel, height))| match panel {
+                panel::Panel::Volume => self.draw_volume_chart(index, height, points.clone()),
+                panel::Panel::Oscillator(oscillator) => self.draw_oscillator_chart(index, height, oscillator, &points),
+            })
+            .collect()
+    }
+
+    /// Draw the volume of the points as a histogram sharing the columns of the chart above
+    fn draw_volume_chart<P>(&self, panel: usize, height: CoordinatePrecision, points: Vec<P>) -> String
+        where P: matrix::PointTrait + PriceTrait {
+        let points: Vec<P> = points.iter()
+            .map(|point| matrix::PointTrait::with_value(point, point.volume().unwrap_or(0.0)))
+            .collect();
+        let max_volume = points.iter().map(matrix::PointTrait::value).fold(0.0, f64::max);
+        let conf = configuration::CallbackConfiguration::new(
+            |_: Option<&matrix::Row<histogram::HistogramCell<P>>>, row_number: CoordinatePrecision| {
+                volume::draw_row(row_number, height, max_volume, self.chart.y_scala_width as usize)
+            },
+            |cell: Option<histogram::HistogramCell<P>>| match cell {
+                Some(ref cell) if cell.kind != histogram::HistogramPart::Top(0) => color::dark_gray(cell.kind.glyph()),
+                _ => self.space.to_string(),
+            },
+        );
+        let conf = configuration::XScalaConfiguration::new(&conf, |columns: &[Option<histogram::HistogramCell<P>>], height| self.draw_x_scala(columns, height));
+
+        self.chart.draw_histogram_with_configuration(panel, points, &conf)
+    }
+
+    /// Draw the lines of the oscillator computed from the points' prices, sharing the columns of the chart
+    /// above
+    ///
+    /// The lines are drawn in the colors of the series on the oscillator's own scale, with its thresholds
+    /// as reference lines
+    fn draw_oscillator_chart<P>(&self, panel: usize, height: CoordinatePrecision, oscillator: rate::Oscillator, points: &[P]) -> String
+        where P: matrix::PointTrait + PriceTrait {
+        let prices: Vec<f64> = points.iter().map(|point| point.price().to_f64()).collect();
+        let lines = oscillator.compute(&prices);
+        // Points without a value keep the columns of the lines aligned with the chart
+        let series: Vec<Vec<P>> = lines.iter()
+            .map(|line| {
+                points.iter().zip(line)
+                    .map(|(point, value)| matrix::PointTrait::with_value(point, value.unwrap_or(f64::NAN)))
+                    .collect()
+            })
+            .collect();
+        let y_range = oscillator.range(&lines);
+        let precision = self.time_series.last().map_or(0, |rate| rate.currency.precision());
+        let labels = self::oscillator::labels(oscillator, &y_range, self.chart.panel_scale_y(panel, &y_range), height, precision);
+        let conf = configuration::CallbackConfiguration::new(
+            |_: Option<&matrix::Row<line::LineCell<series::SeriesCell<P>>>>, row_number: CoordinatePrecision| {
+                self::oscillator::draw_row(row_number, &labels, self.chart.y_scala_width as usize)
+            },
+            |cell: Option<line::LineCell<series::SeriesCell<P>>>| match cell {
+                Some(cell) => colorize_series(cell.point.kind, cell.kind.glyph()),
+                None => self.space.to_string(),
+            },
+        );
+        let conf = configuration::XScalaConfiguration::new(&conf, |columns: &[Option<line::LineCell<series::SeriesCell<P>>>], height| self.draw_x_scala(columns, height));
+        let thresholds = oscillator.thresholds().into_iter()
+            .map(|threshold| configuration::ReferenceLine::new(threshold, "\u{2508}", color::dark_gray, &format!(" {}", threshold)))
+            .collect();
+        let conf = configuration::ReferenceLineConfiguration::new(&conf, thresholds);
+
+        self.chart.draw_panel_lines_with_configuration(panel, series, y_range, &conf)
+    }
+
+    /// Draw the points, each colored by its change from the point before it
+    fn draw_chart<P>(&self, points: Vec<P>) -> String
+        where P: matrix::PointTrait + PriceTrait {
+        let points = trend::with_trends(self.place_on_y_axis(points));
+        match self.chart.style {
+            Style::Line => return self.draw_line_chart(points),
+            Style::Area => return self.draw_area_chart(points),
+            _ => {}
+        }
+        let y_scala = self.get_y_scala(&points);
+        let conf = configuration::GlyphCallbackConfiguration::new(
+            self.fill,
+            |_: Option<&matrix::Row<trend::TrendCell<P>>>, row_number: CoordinatePrecision| self.draw_row(&y_scala, row_number),
+            |point: Option<trend::TrendCell<P>>, glyph: &str| match point {
+                Some(point) => trend::colorize(point.kind, self.mark_out_of_range(matrix::PointTrait::value(&point), glyph)),
+                None => self.space.to_string(),
+            },
+        );
+
+        let conf = configuration::XScalaConfiguration::new(&conf, |columns: &[Option<trend::TrendCell<P>>], height| self.draw_x_scala(columns, height));
+        let conf = configuration::ReferenceLineConfiguration::new(&conf, self.get_reference_lines());
+
+        self.chart.draw_points_with_configuration(points, &conf)
+    }
 
-        util::str_left_pad(
-            &header,
-            self.chart.y_scala_width as usize,
-            ' ',
-        ).to_string()
+    /// Draw the points connected by a line, each segment colored by the change of the point it leads to
+    fn draw_line_chart<P>(&self, points: Vec<trend::TrendCell<P>>) -> String
+        where P: matrix::PointTrait + PriceTrait {
+        let y_scala = self.get_y_scala(&points);
+        let conf = configuration::CallbackConfiguration::new(
+            |_: Option<&matrix::Row<line::LineCell<trend::TrendCell<P>>>>, row_number: CoordinatePrecision| self.draw_row(&y_scala, row_number),
+            |cell: Option<line::LineCell<trend::TrendCell<P>>>| match cell {
+                // Only the segments ending in the point's row are marked
+                Some(ref cell) if is_line_end(cell.kind) => {
+                    trend::colorize(cell.point.kind, self.mark_out_of_range(matrix::PointTrait::value(cell), cell.kind.glyph()))
+                }
+                Some(cell) => trend::colorize(cell.point.kind, cell.kind.glyph()),
+                None => self.space.to_string(),
+            },
+        );
+
+        let conf = configuration::XScalaConfiguration::new(&conf, |columns: &[Option<line::LineCell<trend::TrendCell<P>>>], height| self.draw_x_scala(columns, height));
+        let conf = configuration::ReferenceLineConfiguration::new(&conf, self.get_reference_lines());
+
+        self.chart.draw_line_with_configuration(points, &conf)
     }
 
-    fn get_chart(&self, rate: &rate::Rate, last_rate: &Option<rate::Rate>) -> String {
+    /// Draw the points and the area below them, each column colored by the change of its point
+    fn draw_area_chart<P>(&self, points: Vec<trend::TrendCell<P>>) -> String
+        where P: matrix::PointTrait + PriceTrait {
+        let y_scala = self.get_y_scala(&points);
         let conf = configuration::CallbackConfiguration::new(
-            |row: Option<&matrix::Row<rate::Rate>>, row_number: CoordinatePrecision| self.draw_row(row, row_number),
-            |point: Option<rate::Rate>| self.draw_callback(&rate, last_rate, point),
+            |_: Option<&matrix::Row<area::AreaCell<trend::TrendCell<P>>>>, row_number: CoordinatePrecision| self.draw_row(&y_scala, row_number),
+            |cell: Option<area::AreaCell<trend::TrendCell<P>>>| match cell {
+                Some(ref cell) if cell.kind == area::AreaPart::Fill => {
+                    color::style(&trend::colorize(cell.point.kind, self.area_fill), color::DIM)
+                }
+                Some(ref cell) => trend::colorize(cell.point.kind, self.mark_out_of_range(matrix::PointTrait::value(cell), self.fill)),
+                None => self.space.to_string(),
+            },
+        );
+
+        let conf = configuration::XScalaConfiguration::new(&conf, |columns: &[Option<area::AreaCell<trend::TrendCell<P>>>], height| self.draw_x_scala(columns, height));
+        let conf = configuration::ReferenceLineConfiguration::new(&conf, self.get_reference_lines());
+
+        self.chart.draw_area_with_configuration(points, &conf)
+    }
+
+    fn draw_candlestick_chart(&self, candles: Vec<rate::Candle>) -> String {
+        let points = self.place_on_y_axis(candles.iter().flat_map(|candle| candle.to_points()).collect());
+        let y_scala = self.get_y_scala(&points);
+        let conf = configuration::CallbackConfiguration::new(
+            |_: Option<&matrix::Row<rate::Candle>>, row_number: CoordinatePrecision| self.draw_row(&y_scala, row_number),
+            |point: Option<rate::Candle>| self.draw_candle_callback(point),
+        );
+
+        let conf = configuration::XScalaConfiguration::new(&conf, |columns: &[Option<rate::Candle>], height| self.draw_x_scala(columns, height));
+        let conf = configuration::ReferenceLineConfiguration::new(&conf, self.get_reference_lines());
+
+        self.chart.draw_candles_with_configuration(points, &conf)
+    }
+
+    /// Draw the rates of the currency, of the compared ones and the lines of the indicators, each in its
+    /// own color
+    ///
+    /// The series are drawn as points or lines and the currency is drawn on top of the others. Unless
+    /// currencies are compared, the currency's points are colored by their trend
+    fn draw_series_chart(&self) -> String {
+        let mut colored_series: Vec<(usize, Vec<rate::Rate>)> = self.get_series().into_iter().enumerate()
+            .map(|(index, (anchor, data))| (index, place_relative_to(build_points_from_time_series(data, self.chart.x_axis), anchor)))
+            .collect();
+        colored_series.extend(self.get_indicator_series());
+        let (colors, series): (Vec<usize>, Vec<Vec<SeriesPoint>>) = colored_series.into_iter()
+            .map(|(color, points)| (color, trend::with_trends(points)))
+            .unzip();
+        let y_scala = self.get_y_scala(&series.concat());
+        let colorize = |index: usize, trend: i8, text: &str| {
+            if index == 0 && self.compared.is_empty() {
+                trend::colorize(trend, text)
+            } else {
+                colorize_series(colors[index], text)
+            }
+        };
+
+        if self.chart.style == Style::Line {
+            let conf = configuration::CallbackConfiguration::new(
+                |_: Option<&matrix::Row<line::LineCell<series::SeriesCell<SeriesPoint>>>>, row_number: CoordinatePrecision| self.draw_row(&y_scala, row_number),
+                |cell: Option<line::LineCell<series::SeriesCell<SeriesPoint>>>| match cell {
+                    Some(ref cell) if is_line_end(cell.kind) => {
+                        colorize(cell.point.kind, cell.point.point.kind, self.mark_out_of_range(matrix::PointTrait::value(cell), cell.kind.glyph()))
+                    }
+                    Some(cell) => colorize(cell.point.kind, cell.point.point.kind, cell.kind.glyph()),
+                    None => self.space.to_string(),
+                },
+            );
+            let conf = configuration::XScalaConfiguration::new(&conf, |columns: &[Option<line::LineCell<series::SeriesCell<SeriesPoint>>>], height| self.draw_x_scala(columns, height));
+            let conf = configuration::ReferenceLineConfiguration::new(&conf, self.get_reference_lines());
+
+            return self.chart.draw_series_lines_with_configuration(series, &conf);
+        }
+
+        let conf = configuration::GlyphCallbackConfiguration::new(
+            self.fill,
+            |_: Option<&matrix::Row<series::SeriesCell<SeriesPoint>>>, row_number: CoordinatePrecision| self.draw_row(&y_scala, row_number),
+            |cell: Option<series::SeriesCell<SeriesPoint>>, glyph: &str| match cell {
+                Some(ref cell) => colorize(cell.kind, cell.point.kind, self.mark_out_of_range(matrix::PointTrait::value(cell), glyph)),
+                None => self.space.to_string(),
+            },
         );
+        let conf = configuration::XScalaConfiguration::new(&conf, |columns: &[Option<series::SeriesCell<SeriesPoint>>], height| self.draw_x_scala(columns, height));
+        let conf = configuration::ReferenceLineConfiguration::new(&conf, self.get_reference_lines());
 
-        self.chart.draw_points_with_configuration(
-            build_points_from_time_series(&self.time_series),
-            &conf,
-        )
+        self.chart.draw_series_with_configuration(series, &conf)
+    }
+
+    /// Return the legend naming the color and the change of each compared series and the color of each indicator
+    ///
+    /// Compared currencies which could not be fetched in the latest run are marked with an error
+    fn get_legend(&self) -> String {
+        let mut entries: Vec<String> = vec![];
+        if !self.compared.is_empty() {
+            entries.extend(self.get_series().iter().enumerate()
+                .filter_map(|(index, &(anchor, data))| {
+                    if index > 0 && self.failed_compared.contains(&(index - 1)) {
+                        let symbol = self.compared[index - 1].0.symbol();
+                        return Some(colorize_series(index, &format!("{} {} error", LEGEND_SYMBOL, symbol)));
+                    }
+                    let last = data.last()?;
+                    let change = anchor.map_or(0.0, |anchor| y_scala::percent_change(last.price(), anchor));
+
+                    Some(colorize_series(index, &format!("{} {} {:+.2}%", LEGEND_SYMBOL, last.currency.symbol(), change)))
+                }));
+        }
+        entries.extend(self.indicators.iter().enumerate()
+            .map(|(index, indicator)| colorize_series(self.get_indicator_color(index), &format!("{} {}", LEGEND_SYMBOL, indicator.name()))));
+
+        entries.join(" ")
     }
 
     fn get_footer(&self, rate: &rate::Rate, last_rate: &Option<rate::Rate>) -> String {
-        let now: DateTime<Local> = Local::now();
+        let time: DateTime<Local> = rate.time().with_timezone(&Local);
+        let age = rate.age(Utc::now());
 
-        let col_1 = now.format("%Y-%m-%d %H:%M:%S%.3f").to_string();
+        let col_1 = format!(
+            "{} ({} ago)",
+            time.format("%Y-%m-%d %H:%M:%S"),
+            util::format_duration(age.num_seconds())
+        );
         let col_2 = trend::get_trend_sign(&rate, last_rate, true);
+        let precision = rate.currency.precision();
         let col_3 = format!(
-            "{} ${} / €{}",
+            "{} {} {} ",
             util::str_pad(&rate.currency.symbol(), 5, ' '),
-            util::str_pad(&rate.price_usd.to_string(), 10, ' '),
-            util::str_pad(&rate.price_eur.to_string(), 10, ' ')
+            util::str_pad(&format!("{:.*}", precision, rate.price()), 10, ' '),
+            rate.quote()
         );
 
         let mut footer = format!("{} {} {}", col_1, col_2, col_3);
 
+        let legend = self.get_legend();
+        let space_left = self.chart.width() as isize - PixelSequence::from_str(&footer).len() as isize;
+        if !legend.is_empty() && space_left > PixelSequence::from_str(&legend).len() as isize {
+            footer.push_str(&legend);
+            footer.push(' ');
+        }
+
         let col_4 = match self.value {
             Some(value) => {
                 format!(
-                    "| {} ≈ €{}",
-                    value.to_string(),
-                    util::str_pad(&format!("{}", value * rate.price_eur), 10, ' '),
+                    "| {} ≈ {:.*} {}",
+                    value,
+                    precision,
+                    value * rate.price(),
+                    rate.quote(),
                 )
             }
             None => "".to_string(),
@@ -153,15 +725,14 @@ impl<'a> RatePrinter<'a> {
         "".to_string()
     }
 
-    fn draw_callback<P: matrix::PointTrait>(&self, current_rate: &rate::Rate, last_rate: &Option<rate::Rate>, point: Option<P>) -> String {
+    fn draw_candle_callback(&self, point: Option<rate::Candle>) -> String {
         match point {
-            Some(_) => {
-                let trend = trend::get_trend(current_rate, last_rate);
-                match trend {
-                    -1 => color::red(self.fill),
-                    1 => color::green(self.fill),
-                    0 => color::dark_gray(self.fill),
-                    _ => color::bg_red(self.fill),
+            Some(candle) => {
+                let glyph = self.mark_out_of_range(matrix::PointTrait::value(&candle), candlestick::CandlePointTrait::part(&candle).glyph());
+                if candle.is_bullish() {
+                    color::green(glyph)
+                } else {
+                    color::red(glyph)
                 }
             }
             None => self.space.to_string(),
@@ -171,23 +742,60 @@ impl<'a> RatePrinter<'a> {
     /// Return the `Screen` for this run
     ///
     /// If the terminal size did not change since the previous run, the last `Screen` will be reused
-    fn get_screen(&mut self) -> &mut Screen<Terminal> {
-        if self.screen.size() == Size::auto().unwrap() {
-            return &mut self.screen;
+    fn get_screen(&mut self) -> Result<&mut Screen<Terminal>, Error> {
+        let size = Size::auto()?;
+        if self.screen.as_ref().is_none_or(|screen| screen.size() != size) {
+            self.screen = Some(Screen::default()?);
         }
-        self.screen = Screen::default().unwrap();
 
-        &mut self.screen
+        Ok(self.screen.as_mut().unwrap())
     }
 }
 
 
-fn build_points_from_time_series(time_series: &RateSeries) -> Vec<rate::Rate> {
-    let mut points: Vec<rate::Rate> = vec![];
-    let mut len = 0;
-    for rate in time_series.data() {
-        points.push(matrix::PointTrait::with_x(rate, len));
-        len += 1;
+/// Place the points vertically as the change from the anchor, or by their price if there is none
+fn place_relative_to<P: matrix::PointTrait + PriceTrait>(points: Vec<P>, anchor: Option<rate::Price>) -> Vec<P> {
+    match anchor {
+        Some(anchor) => points.iter()
+            .map(|point| point.with_value(rate::Rate::price_to_value_relative(point.price(), anchor)))
+            .collect(),
+        None => points,
+    }
+}
+
+/// Color the text in the color of the series with the given index
+fn colorize_series(index: usize, text: &str) -> String {
+    SERIES_COLORS[index % SERIES_COLORS.len()](text)
+}
+
+/// Return if the line segment is drawn in the row of the point it belongs to
+fn is_line_end(segment: line::LineSegment) -> bool {
+    matches!(segment, line::LineSegment::Horizontal | line::LineSegment::RiseEnd | line::LineSegment::FallEnd | line::LineSegment::Peak | line::LineSegment::Trough)
+}
+
+fn build_points_from_time_series<P>(time_series: &[P], x_axis: XAxis) -> Vec<P>
+    where P: matrix::PointTrait + PriceTrait {
+    let mut points: Vec<P> = vec![];
+    match x_axis {
+        XAxis::Index => {
+            let mut len = 0;
+            for rate in time_series {
+                points.push(matrix::PointTrait::with_x(rate, len));
+                len += 1;
+            }
+        }
+        XAxis::Time(seconds_per_column) => {
+            // Columns are aligned to multiples of their duration, so they don't shift between runs
+            let first_column = match time_series.iter().map(|rate| rate.time().timestamp()).min() {
+                Some(timestamp) => timestamp.div_euclid(seconds_per_column),
+                None => return points,
+            };
+            for rate in time_series {
+                let column = rate.time().timestamp().div_euclid(seconds_per_column) - first_column;
+                let x = column.min(CoordinatePrecision::MAX as i64) as CoordinatePrecision;
+                points.push(matrix::PointTrait::with_x(rate, x));
+            }
+        }
     }
 
     points
@@ -207,10 +815,100 @@ fn build_time_series(chart: &Chart, history_size: Option<usize>) -> RateSeries {
             if chart_width <= chart.y_scala_width {
                 error!("Chart width must be bigger than {}", chart.y_scala_width)
             } else {
-                (chart_width - chart.y_scala_width) as usize
+                chart.point_columns()
             }
         }
     };
 
     rate::RateSeries::new(prepared_history_size)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use matrix::PointTrait;
+
+    fn build_time_series_with_times(timestamps: &[i64]) -> RateSeries {
+        let mut time_series = RateSeries::new(timestamps.len());
+        for timestamp in timestamps {
+            let rate = rate::Rate::from_price(rate::Currency::bitcoin(), "USD", rate::Price::from_f64(1.0));
+            time_series.push(rate.with_source_time(Utc.timestamp_opt(*timestamp, 0).single()));
+        }
+
+        time_series
+    }
+
+    #[test]
+    fn build_points_by_index_test() {
+        let time_series = build_time_series_with_times(&[1_000, 1_100, 1_105, 5_000]);
+        let columns: Vec<CoordinatePrecision> = build_points_from_time_series(time_series.data(), XAxis::Index)
+            .iter().map(|p| p.x()).collect();
+
+        assert_eq!(vec![0, 1, 2, 3], columns);
+    }
+
+    #[test]
+    fn build_points_by_time_test() {
+        // The missing samples between 1_260 and 1_500 must leave empty columns
+        let time_series = build_time_series_with_times(&[1_210, 1_230, 1_260, 1_500]);
+        let columns: Vec<CoordinatePrecision> = build_points_from_time_series(time_series.data(), XAxis::Time(60))
+            .iter().map(|p| p.x()).collect();
+
+        assert_eq!(vec![0, 0, 1, 5], columns);
+    }
+
+    #[test]
+    fn build_points_by_time_empty_test() {
+        assert!(build_points_from_time_series(RateSeries::new(2).data(), XAxis::Time(60)).is_empty());
+    }
+
+    #[test]
+    fn build_points_from_candles_test() {
+        let mut time_series = RateSeries::with_candles(10, 60, 10);
+        for timestamp in &[1_210, 1_230, 1_260, 1_500] {
+            let rate = rate::Rate::from_price(rate::Currency::bitcoin(), "USD", rate::Price::from_f64(1.0));
+            time_series.push(rate.with_source_time(Utc.timestamp_opt(*timestamp, 0).single()));
+        }
+
+        let by_index: Vec<CoordinatePrecision> = build_points_from_time_series(time_series.candles(), XAxis::Index)
+            .iter().map(|p| p.x()).collect();
+        assert_eq!(vec![0, 1, 2], by_index);
+
+        let by_time: Vec<CoordinatePrecision> = build_points_from_time_series(time_series.candles(), XAxis::Time(60))
+            .iter().map(|p| p.x()).collect();
+        assert_eq!(vec![0, 1, 5], by_time);
+    }
+
+    #[test]
+    fn get_indicator_color_test() {
+        let mut printer = RatePrinter::new(Chart::new(40, 10, 2, 10, Mode::Scale), None, "faker", "USD", "*", " ", Some(10)).unwrap();
+        assert_eq!(1, printer.get_indicator_color(0));
+
+        for currency in &["eth", "ltc", "xmr"] {
+            printer.compare_with(rate::Currency::new(*currency, *currency, vec![], 2));
+        }
+        assert_eq!(4, printer.get_indicator_color(0));
+        // The colors of further indicators wrap around without reaching the currency's color
+        assert_eq!(7, printer.get_indicator_color(3));
+        assert_eq!(1, printer.get_indicator_color(4));
+    }
+
+    #[test]
+    fn get_legend_test() {
+        let currency = |symbol: &str| rate::Currency::new(symbol, symbol, vec![], 2);
+        let rate = |currency: &rate::Currency, price: &str| rate::Rate::from_price(currency.clone(), "USD", price.parse().unwrap());
+        let mut printer = RatePrinter::new(Chart::new(40, 10, 2, 10, Mode::Scale), None, "faker", "USD", "*", " ", Some(10)).unwrap();
+        printer.time_series.push(rate(&currency("BTC"), "100"));
+        printer.compare_with(currency("ETH"));
+        printer.compare_with(currency("LTC"));
+        printer.compared[0].1.push(rate(&currency("ETH"), "10"));
+        printer.compared[1].1.push(rate(&currency("LTC"), "5"));
+
+        // A compared currency which could not be fetched is marked, the others are still drawn
+        printer.failed_compared = vec![1];
+        assert_eq!(
+            [colorize_series(0, "\u{25a0} BTC +0.00%"), colorize_series(1, "\u{25a0} ETH +0.00%"), colorize_series(2, "\u{25a0} LTC error")].join(" "),
+            printer.get_legend()
+        );
+    }
+}
@@ -0,0 +1,90 @@
+use std::collections::BTreeMap;
+use chart::ScaleY;
+use chart::YRange;
+use rate::Oscillator;
+use ui::CoordinatePrecision;
+use super::panel;
+
+/// Format a value on the oscillator's scale (MACD values are price differences shown with the currency's precision)
+fn format_value(oscillator: Oscillator, value: f64, precision: usize) -> String {
+    match oscillator {
+        Oscillator::Rsi(_) => format!("{:.0}", value),
+        Oscillator::Macd(..) if value == 0.0 => "0".to_string(),
+        Oscillator::Macd(..) => format!("{:+.*}", precision, value),
+    }
+}
+
+/// Return the labels of the rows of a panel with `height` rows drawing the oscillator over `y_range`
+///
+/// The bottom and the top row are labeled with the ends of the range and the row holding zero with "0". The
+/// name of the oscillator is placed on the row below the top one, if that row is left
+pub fn labels(oscillator: Oscillator, y_range: &YRange, scale_y: ScaleY, height: CoordinatePrecision, precision: usize) -> BTreeMap<CoordinatePrecision, String> {
+    let mut labels = BTreeMap::new();
+    if height == 0 {
+        return labels;
+    }
+    let (min, max) = y_range.resolve(0.0, 1.0);
+    labels.insert(height - 1, format_value(oscillator, max, precision));
+    labels.entry(0).or_insert_with(|| format_value(oscillator, min, precision));
+    if min < 0.0 && max > 0.0 {
+        labels.entry(scale_y.apply(0.0)).or_insert_with(|| format_value(oscillator, 0.0, precision));
+    }
+    if height >= 3 {
+        labels.entry(height - 2).or_insert_with(|| oscillator.name());
+    }
+
+    labels
+}
+
+/// Draw the label of the given row padded to `width`
+pub fn draw_row(row_number: CoordinatePrecision, labels: &BTreeMap<CoordinatePrecision, String>, width: usize) -> String {
+    panel::draw_label(labels.get(&row_number).map(String::as_str), width)
+}
+
+/// Return the width of the widest label of the oscillator drawn over `y_range`
+pub fn label_width(oscillator: Oscillator, y_range: &YRange, precision: usize) -> usize {
+    let (min, max) = y_range.resolve(0.0, 1.0);
+
+    [oscillator.name(), format_value(oscillator, min, precision), format_value(oscillator, max, precision)].iter()
+        .map(|label| panel::label_width(label))
+        .max()
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn labels_test() {
+        let rsi = Oscillator::Rsi(14);
+        let y_range = YRange { min: Some(0.0), max: Some(100.0) };
+        let rows = labels(rsi, &y_range, ScaleY::Linear { factor: 0.03, origin: 0.0 }, 4, 2);
+        assert_eq!(vec![(0, "0"), (2, "RSI(14)"), (3, "100")], rows.iter().map(|(row, label)| (*row, label.as_str())).collect::<Vec<_>>());
+
+        let macd = Oscillator::Macd(12, 26, 9);
+        let y_range = YRange { min: Some(-1.5), max: Some(1.5) };
+        let rows = labels(macd, &y_range, ScaleY::Linear { factor: 1.0, origin: -1.5 }, 4, 2);
+        assert_eq!(vec![(0, "-1.50"), (1, "0"), (2, "MACD(12,26,9)"), (3, "+1.50")], rows.iter().map(|(row, label)| (*row, label.as_str())).collect::<Vec<_>>());
+
+        // Two rows only fit the ends of the range
+        let rows = labels(macd, &y_range, ScaleY::Linear { factor: 1.0 / 3.0, origin: -1.5 }, 2, 2);
+        assert_eq!(vec![(0, "-1.50"), (1, "+1.50")], rows.iter().map(|(row, label)| (*row, label.as_str())).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn draw_row_test() {
+        let labels: BTreeMap<CoordinatePrecision, String> = vec![(0, "0".to_string()), (2, "RSI(14)".to_string())].into_iter().collect();
+        assert_eq!("      0 |", draw_row(0, &labels, 9));
+        assert_eq!("        |", draw_row(1, &labels, 9));
+        assert_eq!("RSI(14) |", draw_row(2, &labels, 9));
+        assert_eq!("    |", draw_row(2, &labels, 5));
+    }
+
+    #[test]
+    fn label_width_test() {
+        assert_eq!(9, label_width(Oscillator::Rsi(14), &YRange { min: Some(0.0), max: Some(100.0) }, 2));
+        assert_eq!(15, label_width(Oscillator::Macd(12, 26, 9), &YRange { min: Some(-1.5), max: Some(1.5) }, 2));
+        assert_eq!(13, label_width(Oscillator::Macd(1, 2, 3), &YRange { min: Some(-1234.5), max: Some(1234.5) }, 2));
+    }
+}
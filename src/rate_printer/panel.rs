@@ -0,0 +1,91 @@
+use rate::Oscillator;
+use ui::CoordinatePrecision;
+use util;
+
+/// Share of the chart's rows taken by each panel (one in `HEIGHT_SHARE`)
+const HEIGHT_SHARE: CoordinatePrecision = 5;
+
+/// Minimum number of rows of a panel
+const MIN_HEIGHT: CoordinatePrecision = 2;
+
+/// Minimum number of rows left for the rates above the panels
+const MIN_RATES_HEIGHT: CoordinatePrecision = 3;
+
+/// Sub-chart stacked below the rates, sharing their columns
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Panel {
+    /// Histogram of the trading volume
+    Volume,
+    /// Lines of the oscillator on its own scale
+    Oscillator(Oscillator),
+}
+
+/// Return the panels fitting below the rates of a chart with `available` rows, each with its number of rows
+///
+/// All panels get the same number of rows. Panels which would leave too few rows for the rates are left out
+pub fn layout(panels: &[Panel], available: CoordinatePrecision) -> Vec<(Panel, CoordinatePrecision)> {
+    let height = (available / HEIGHT_SHARE).max(MIN_HEIGHT);
+    let mut remaining = available;
+    let mut result = vec![];
+    for panel in panels {
+        if remaining >= height + MIN_RATES_HEIGHT {
+            remaining -= height;
+            result.push((*panel, height));
+        }
+    }
+
+    result
+}
+
+/// Draw the label of the given row of a panel with `height` rows padded to `width`
+///
+/// Only the top row is labeled. Labels too wide for the scala are left out
+pub fn draw_row(row_number: CoordinatePrecision, height: CoordinatePrecision, label: &str, width: usize) -> String {
+    draw_label(if row_number + 1 == height { Some(label) } else { None }, width)
+}
+
+/// Draw the label of a row padded to `width`
+///
+/// Labels too wide for the scala are left out
+pub fn draw_label(label: Option<&str>, width: usize) -> String {
+    let header = match label {
+        Some(label) if label_width(label) <= width => format_label(label),
+        _ => "|".to_string(),
+    };
+
+    util::str_left_pad(&header, width, ' ').to_string()
+}
+
+/// Return the width of the label drawn by `draw_row()`
+pub fn label_width(label: &str) -> usize {
+    format_label(label).chars().count()
+}
+
+fn format_label(label: &str) -> String {
+    format!("{} |", label)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layout_test() {
+        assert_eq!(vec![(Panel::Volume, 4)], layout(&[Panel::Volume], 20));
+        assert_eq!(vec![(Panel::Volume, 2)], layout(&[Panel::Volume], 6));
+        assert_eq!(vec![(Panel::Volume, 2)], layout(&[Panel::Volume], 5));
+        assert!(layout(&[Panel::Volume], 4).is_empty());
+
+        let panels = [Panel::Volume, Panel::Oscillator(Oscillator::Rsi(14))];
+        assert_eq!(vec![(panels[0], 4), (panels[1], 4)], layout(&panels, 20));
+        // The oscillator would leave too few rows for the rates
+        assert_eq!(vec![(panels[0], 2)], layout(&panels, 6));
+    }
+
+    #[test]
+    fn draw_row_test() {
+        assert_eq!("  RSI(14) |", draw_row(2, 3, "RSI(14)", 11));
+        assert_eq!("          |", draw_row(1, 3, "RSI(14)", 11));
+        assert_eq!("    |", draw_row(2, 3, "RSI(14)", 5));
+    }
+}
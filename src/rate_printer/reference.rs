@@ -0,0 +1,100 @@
+use chart::configuration::ReferenceLine;
+use rate::Price;
+use rate::PriceTrait;
+use term_style::style as color;
+
+/// Horizontal guide line drawn across the chart, parsed from "open", "average" or `PRICE[:LABEL]`
+#[derive(Debug, Clone, PartialEq)]
+pub enum Reference {
+    /// The first rate in the history
+    Open,
+    /// The mean of the rates in the history
+    Average,
+    /// A price given by the user (e.g. an entry price) with an optional label
+    Level(Price, Option<String>),
+}
+
+impl Reference {
+    pub fn from_str(input: &str) -> Result<Self, ()> {
+        match input.trim().to_lowercase().as_ref() {
+            "open" => return Ok(Reference::Open),
+            "average" | "avg" => return Ok(Reference::Average),
+            _ => {}
+        }
+        let mut parts = input.splitn(2, ':').map(str::trim);
+        let price: Price = parts.next().unwrap_or("").parse()?;
+        let label = parts.next().filter(|label| !label.is_empty()).map(str::to_string);
+
+        Ok(Reference::Level(price, label))
+    }
+
+    /// Return the price of the reference for the rates, if it is known
+    pub fn price<P: PriceTrait>(&self, rates: &[P]) -> Option<Price> {
+        match *self {
+            Reference::Open => rates.first().map(|rate| rate.price()),
+            Reference::Average if rates.is_empty() => None,
+            Reference::Average => {
+                let sum: f64 = rates.iter().map(|rate| rate.price().to_f64()).sum();
+                Some(Price::from_f64(sum / rates.len() as f64))
+            }
+            Reference::Level(price, _) => Some(price),
+        }
+    }
+
+    /// Build the line at `value` labeled with the name of the reference and the price
+    pub fn build_line(&self, value: f64, price: Price, precision: usize) -> ReferenceLine {
+        let (glyph, color, name): (&str, fn(&str) -> String, &str) = match *self {
+            Reference::Open => ("\u{2508}", color::yellow, "open"),
+            Reference::Average => ("\u{254c}", color::cyan, "avg"),
+            Reference::Level(_, ref label) => ("\u{2504}", color::light_magenta, label.as_ref().map_or("", String::as_str)),
+        };
+        let label = if name.is_empty() {
+            format!(" {:.*}", precision, price)
+        } else {
+            format!(" {} {:.*}", name, precision, price)
+        };
+
+        ReferenceLine::new(value, glyph, color, &label)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rate::Currency;
+    use rate::Rate;
+
+    #[test]
+    fn from_str_test() {
+        assert_eq!(Ok(Reference::Open), Reference::from_str("open"));
+        assert_eq!(Ok(Reference::Average), Reference::from_str("AVG"));
+        assert_eq!(Ok(Reference::Level("17000".parse().unwrap(), None)), Reference::from_str("17000"));
+        assert_eq!(Ok(Reference::Level("0.5".parse().unwrap(), Some("entry".to_string()))), Reference::from_str("0.5:entry"));
+        assert!(Reference::from_str("entry").is_err());
+        assert!(Reference::from_str(":entry").is_err());
+    }
+
+    #[test]
+    fn price_test() {
+        let rates: Vec<Rate> = ["100", "104", "99"].iter()
+            .map(|price| Rate::from_price(Currency::bitcoin(), "USD", price.parse().unwrap()))
+            .collect();
+
+        assert_eq!("100", Reference::Open.price(&rates).unwrap().to_string());
+        assert_eq!("101", Reference::Average.price(&rates).unwrap().to_string());
+        assert_eq!("7", Reference::Level("7".parse().unwrap(), None).price(&rates).unwrap().to_string());
+        assert!(Reference::Open.price::<Rate>(&[]).is_none());
+        assert!(Reference::Average.price::<Rate>(&[]).is_none());
+    }
+
+    #[test]
+    fn build_line_test() {
+        let line = Reference::Level("17000".parse().unwrap(), Some("entry".to_string())).build_line(5.0, "17000".parse().unwrap(), 2);
+        assert_eq!(5.0, line.value);
+        assert_eq!(" entry 17000.00", line.label);
+
+        let line = Reference::Level("17000".parse().unwrap(), None).build_line(5.0, "17000".parse().unwrap(), 0);
+        assert_eq!(" 17000", line.label);
+        assert_eq!(" avg 1.5", Reference::Average.build_line(1.0, "1.5".parse().unwrap(), 1).label);
+    }
+}
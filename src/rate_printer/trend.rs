@@ -1,7 +1,14 @@
 use rate;
+use rate::Price;
+use rate::PriceTrait;
 use rate::RateSeries;
+use chart::cell::Cell;
+use matrix::PointTrait;
 use term_style::style as color;
 
+/// Point holding its trend (see `get_price_trend()`)
+pub type TrendCell<P> = Cell<P, i8>;
+
 #[allow(unused)]
 pub fn get_trend_from_time_series(current_rate: &rate::Rate, time_series: &RateSeries) -> i8 {
     get_trend(current_rate, &time_series.last().cloned())
@@ -9,17 +16,50 @@ pub fn get_trend_from_time_series(current_rate: &rate::Rate, time_series: &RateS
 
 #[allow(unused)]
 pub fn get_trend(current_rate: &rate::Rate, last_rate: &Option<rate::Rate>) -> i8 {
-    match last_rate {
-        &Some(ref last_rate) => {
-            if current_rate.price_usd < last_rate.price_usd {
+    get_price_trend(current_rate.price(), last_rate.as_ref().map(|rate| rate.price()))
+}
+
+/// Return -1 if the price fell, 1 if it rose and 0 if it stayed the same (2 if there is no last price)
+pub fn get_price_trend(current_price: Price, last_price: Option<Price>) -> i8 {
+    match last_price {
+        Some(last_price) => {
+            if current_price < last_price {
                 -1
-            } else if current_rate.price_usd > last_rate.price_usd {
+            } else if current_price > last_price {
                 1
             } else {
                 0
             }
         }
-        &None => 2,
+        None => 2,
+    }
+}
+
+/// Attach to each point its trend from the point before it
+///
+/// The first point has nothing to compare to, so it counts as unchanged
+pub fn with_trends<P: PointTrait + PriceTrait>(points: Vec<P>) -> Vec<TrendCell<P>> {
+    let mut last_price: Option<Price> = None;
+    points.into_iter()
+        .map(|point| {
+            let trend = match last_price {
+                Some(_) => get_price_trend(point.price(), last_price),
+                None => 0,
+            };
+            last_price = Some(point.price());
+
+            Cell::new(point, trend)
+        })
+        .collect()
+}
+
+/// Color the text by the trend
+pub fn colorize(trend: i8, text: &str) -> String {
+    match trend {
+        -1 => color::red(text),
+        1 => color::green(text),
+        0 => color::dark_gray(text),
+        _ => color::bg_red(text),
     }
 }
 
@@ -48,3 +88,31 @@ pub fn get_trend_sign(current_rate: &rate::Rate, last_rate: &Option<rate::Rate>,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rate::Currency;
+    use rate::Rate;
+
+    #[test]
+    fn get_price_trend_test() {
+        let price = |price: &str| price.parse::<Price>().unwrap();
+
+        assert_eq!(1, get_price_trend(price("2"), Some(price("1"))));
+        assert_eq!(-1, get_price_trend(price("1"), Some(price("2"))));
+        assert_eq!(0, get_price_trend(price("1"), Some(price("1"))));
+        assert_eq!(2, get_price_trend(price("1"), None));
+    }
+
+    #[test]
+    fn with_trends_test() {
+        let rates: Vec<Rate> = ["100", "104", "104", "99", "101"].iter()
+            .map(|price| Rate::from_price(Currency::bitcoin(), "USD", price.parse().unwrap()))
+            .collect();
+        let trends: Vec<i8> = with_trends(rates).iter().map(|cell| cell.kind).collect();
+
+        // Each point is compared to the one before it, not to the latest rate
+        assert_eq!(vec![0, 1, 0, -1, 1], trends);
+    }
+}
@@ -0,0 +1,50 @@
+use ui::CoordinatePrecision;
+use super::panel;
+
+/// Suffixes for the magnitudes of the volume labels
+const MAGNITUDES: [(f64, &str); 4] = [(1e12, "T"), (1e9, "B"), (1e6, "M"), (1e3, "K")];
+
+/// Format the volume with a suffix for its magnitude (e.g. "72.9M")
+fn format_volume(volume: f64) -> String {
+    for &(magnitude, suffix) in &MAGNITUDES {
+        if volume.abs() >= magnitude {
+            return format!("{:.1}{}", volume / magnitude, suffix);
+        }
+    }
+
+    format!("{:.0}", volume)
+}
+
+/// Draw the label of the given row of a histogram with `height` rows padded to `width`
+///
+/// The top row is labeled with the highest volume, which fills the histogram
+pub fn draw_row(row_number: CoordinatePrecision, height: CoordinatePrecision, max_volume: f64, width: usize) -> String {
+    panel::draw_row(row_number, height, &format_volume(max_volume), width)
+}
+
+/// Return the width of the label of the volume
+pub fn label_width(volume: f64) -> usize {
+    panel::label_width(&format_volume(volume))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_volume_test() {
+        assert_eq!("950", format_volume(950.0));
+        assert_eq!("1.0K", format_volume(1_000.0));
+        assert_eq!("72.9M", format_volume(72_855_700.0));
+        assert_eq!("9.1B", format_volume(9_080_883_500.0));
+        assert_eq!("1.2T", format_volume(1.2e12));
+    }
+
+    #[test]
+    fn draw_row_test() {
+        assert_eq!(" 72.9M |", draw_row(2, 3, 72_855_700.0, 8));
+        assert_eq!("       |", draw_row(1, 3, 72_855_700.0, 8));
+        assert_eq!("     |", draw_row(2, 3, 72_855_700.0, 6));
+        assert_eq!(7, label_width(72_855_700.0));
+    }
+}
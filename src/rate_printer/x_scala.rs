@@ -0,0 +1,127 @@
+use chrono::prelude::*;
+use rate::PriceTrait;
+use util;
+
+/// Minimum number of spaces between two labels
+const LABEL_GAP: usize = 2;
+
+/// Build the x-axis scala with a tick and a time label below the columns that fit
+///
+/// The first line holds the axis and the ticks, the second one the labels. If the scala is only one
+/// line high, only the labels are drawn.
+pub fn draw_x_scala<P: PriceTrait>(columns: &[Option<P>], height: usize, y_scala_width: usize) -> String {
+    if height == 0 {
+        return "".to_string();
+    }
+    let times: Vec<Option<DateTime<Local>>> = columns.iter()
+        .map(|column| column.as_ref().map(|point| point.time().with_timezone(&Local)))
+        .collect();
+    let format = get_time_format(&times);
+
+    let mut axis = String::with_capacity(columns.len());
+    let mut labels = String::with_capacity(columns.len());
+    let mut next_free_column = 0;
+    for (column, time) in times.iter().enumerate() {
+        let label = time.map(|time| time.format(format).to_string());
+        match label {
+            Some(ref label) if column >= next_free_column && column + label.chars().count() <= columns.len() => {
+                axis.push('+');
+                labels.push_str(label);
+                next_free_column = column + label.chars().count() + LABEL_GAP;
+            }
+            _ => {
+                axis.push('-');
+                if column >= labels.chars().count() {
+                    labels.push(' ');
+                }
+            }
+        }
+    }
+
+    let mut lines: Vec<String> = vec![];
+    if height > 1 {
+        lines.push(format!("{}{}", util::str_left_pad("+", y_scala_width, ' '), axis));
+    }
+    lines.push(format!("{}{}", " ".repeat(y_scala_width), labels));
+    while lines.len() < height {
+        lines.push("".to_string());
+    }
+
+    lines.iter().map(|line| format!("{}\n", line)).collect()
+}
+
+/// Return the time format matching the span between the first and the last column
+fn get_time_format(times: &[Option<DateTime<Local>>]) -> &'static str {
+    let first = times.iter().find_map(|time| *time);
+    let last = times.iter().rev().find_map(|time| *time);
+    let span = match (first, last) {
+        (Some(first), Some(last)) => last.signed_duration_since(first).num_seconds(),
+        _ => 0,
+    };
+
+    if span < 10 * 60 {
+        "%H:%M:%S"
+    } else if span < 24 * 60 * 60 {
+        "%H:%M"
+    } else {
+        "%m-%d"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rate;
+
+    fn build_columns(times: &[Option<i64>]) -> Vec<Option<rate::Rate>> {
+        times.iter()
+            .map(|time| time.map(|timestamp| {
+                rate::Rate::from_price(rate::Currency::bitcoin(), "USD", rate::Price::from_f64(1.0))
+                    .with_source_time(Utc.timestamp_opt(timestamp, 0).single())
+            }))
+            .collect()
+    }
+
+    fn local(timestamp: i64, format: &str) -> String {
+        Utc.timestamp_opt(timestamp, 0).unwrap().with_timezone(&Local).format(format).to_string()
+    }
+
+    #[test]
+    fn get_time_format_test() {
+        let at = |timestamp: i64| Some(Utc.timestamp_opt(timestamp, 0).unwrap().with_timezone(&Local));
+
+        assert_eq!("%H:%M:%S", get_time_format(&[]));
+        assert_eq!("%H:%M:%S", get_time_format(&[at(0), None, at(599)]));
+        assert_eq!("%H:%M", get_time_format(&[at(0), at(600)]));
+        assert_eq!("%m-%d", get_time_format(&[None, at(0), at(86_400)]));
+    }
+
+    #[test]
+    fn draw_x_scala_test() {
+        // The label of the last column would not fit
+        let mut times = vec![Some(0)];
+        times.extend(vec![None; 6]);
+        times.push(Some(3_600));
+        times.extend(vec![None; 5]);
+        times.push(Some(7_200));
+        let scala = draw_x_scala(&build_columns(&times), 2, 3);
+
+        assert_eq!(
+            format!(
+                "  ++------+------\n   {}  {}  \n",
+                local(0, "%H:%M"),
+                local(3_600, "%H:%M"),
+            ),
+            scala
+        );
+    }
+
+    #[test]
+    fn draw_x_scala_single_line_test() {
+        let mut times = vec![Some(0), Some(1)];
+        times.extend(vec![None; 8]);
+        let scala = draw_x_scala(&build_columns(&times), 1, 1);
+
+        assert_eq!(format!(" {}  \n", local(0, "%H:%M:%S")), scala);
+    }
+}
@@ -0,0 +1,276 @@
+use rate::Currency;
+use rate::Price;
+use rate::PriceTrait;
+use rate::RELATIVE_SCALE;
+use chart::ScaleY;
+use chart::YRange;
+use ui::CoordinatePrecision;
+use util;
+
+/// Minimum number of rows between two ticks
+const MIN_ROWS_PER_TICK: f64 = 3.0;
+
+/// Number of decimals of percent labels (matching the precision of `RELATIVE_SCALE`)
+const PERCENT_PRECISION: usize = 2;
+
+/// Scala for the y-axis with ticks at round values (e.g. every 50 USD or every 0.5%)
+///
+/// The values are computed from the row numbers, so every row on which a tick falls is labeled, no
+/// matter if it holds a point or not. The distance between the ticks follows the value span of the
+/// rows, so the ticks of a logarithmic scala are log-spaced
+#[derive(Debug, Clone)]
+pub struct YScala {
+    scale_y: ScaleY,
+    /// Number of point values (see `PointTrait::value()`) per unit of the label
+    values_per_unit: f64,
+    /// Number of grid rows a single row spans (more than one for sub-cell renderers)
+    resolution: f64,
+    precision: usize,
+    percent: bool,
+}
+
+impl YScala {
+    /// Build a scala labeled with the prices in the currency's precision
+    pub fn new(currency: &Currency, scale_y: ScaleY, resolution: CoordinatePrecision) -> Self {
+        YScala {
+            scale_y,
+            values_per_unit: f64::from(currency.scale()),
+            resolution: f64::from(resolution.max(1)),
+            precision: currency.precision(),
+            percent: false,
+        }
+    }
+
+    /// Build a scala labeled with the percent change for points with values from
+    /// `Rate::price_to_value_relative()`
+    pub fn percent(scale_y: ScaleY, resolution: CoordinatePrecision) -> Self {
+        YScala {
+            scale_y,
+            values_per_unit: RELATIVE_SCALE / 100.0,
+            resolution: f64::from(resolution.max(1)),
+            precision: PERCENT_PRECISION,
+            percent: true,
+        }
+    }
+
+    /// Return the value of the tick falling on the given row
+    pub fn tick(&self, row_number: CoordinatePrecision) -> Option<f64> {
+        let row_number = f64::from(row_number);
+        let low = self.value_at(row_number);
+        let high = self.value_at(row_number + self.resolution);
+        if high <= low {
+            return None;
+        }
+        let step = nice_number((high - low) * MIN_ROWS_PER_TICK);
+        let tick = match self.scale_y {
+            // Rows spanning a large share of their value in a steep logarithmic price scala only get
+            // powers of ten as ticks
+            ScaleY::Logarithmic { .. } if !self.percent && step >= high => 10.0f64.powf(high.log10().floor()),
+            _ => (low / step).ceil() * step,
+        };
+
+        // Adding zero turns a negative zero into a positive one
+        if tick >= low && tick < high { Some(tick + 0.0) } else { None }
+    }
+
+    /// Draw the label of the given row padded to `width`
+    ///
+    /// Labels too wide for the scala are left out
+    pub fn draw_row(&self, row_number: CoordinatePrecision, width: usize) -> String {
+        let header = match self.tick(row_number) {
+            Some(tick) if label_width(tick, self.precision, self.percent) <= width => {
+                format_label(tick, self.precision, self.percent)
+            }
+            _ => "|".to_string(),
+        };
+
+        util::str_left_pad(&header, width, ' ').to_string()
+    }
+
+    /// Return the lowest value drawn on the given (fractional) row
+    fn value_at(&self, row_number: f64) -> f64 {
+        self.scale_y.invert(row_number) / self.values_per_unit
+    }
+}
+
+/// Return the width of the scala fitting the labels of all the prices
+pub fn get_width<P: PriceTrait>(prices: &[P]) -> usize {
+    prices.iter()
+        .map(|price| label_width(price.price().to_f64(), price.currency().precision(), false))
+        .max()
+        .unwrap_or(0)
+}
+
+/// Return the width of the scala fitting the labels of the change of all the prices from the anchor
+pub fn get_percent_width<P: PriceTrait>(prices: &[P], anchor: Price) -> usize {
+    prices.iter()
+        .map(|price| label_width(percent_change(price.price(), anchor), PERCENT_PRECISION, true))
+        .max()
+        .unwrap_or(0)
+}
+
+/// Return the width of the scala fitting the labels of the bounds of the y-range
+pub fn get_range_width(y_range: &YRange, precision: usize, percent: bool) -> usize {
+    y_range.min.iter().chain(y_range.max.iter())
+        .map(|bound| label_width(*bound, precision, percent))
+        .max()
+        .unwrap_or(0)
+}
+
+/// Return the change from the anchor to the price in percent
+pub fn percent_change(price: Price, anchor: Price) -> f64 {
+    (price.to_f64() / anchor.to_f64() - 1.0) * 100.0
+}
+
+fn format_label(value: f64, precision: usize, percent: bool) -> String {
+    if percent {
+        format!("{:+.*}% |", precision, value)
+    } else {
+        format!("{:.*} |", precision, value)
+    }
+}
+
+fn label_width(value: f64, precision: usize, percent: bool) -> usize {
+    format_label(value, precision, percent).chars().count()
+}
+
+/// Round the value up to the next "nice" number (1, 2 or 5 multiplied by a power of ten)
+fn nice_number(value: f64) -> f64 {
+    if value <= 0.0 || !value.is_finite() {
+        return 1.0;
+    }
+    let magnitude = 10.0f64.powf(value.log10().floor());
+    let fraction = value / magnitude;
+    let nice_fraction = if fraction <= 1.0 {
+        1.0
+    } else if fraction <= 2.0 {
+        2.0
+    } else if fraction <= 5.0 {
+        5.0
+    } else {
+        10.0
+    };
+
+    nice_fraction * magnitude
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nice_number_test() {
+        assert_eq!(1.0, nice_number(0.0));
+        assert_eq!(1.0, nice_number(1.0));
+        assert_eq!(5.0, nice_number(3.0));
+        assert_eq!(50.0, nice_number(42.0));
+        assert_eq!(100.0, nice_number(51.0));
+        assert!((nice_number(0.0013) - 0.002).abs() < 1e-12);
+    }
+
+    #[test]
+    fn tick_test() {
+        // One row per USD, so there is a tick every 5 USD
+        let scala = YScala::new(&Currency::bitcoin(), ScaleY::Linear { factor: 1.0, origin: 0.0 }, 1);
+
+        assert_eq!(Some(10.0), scala.tick(10));
+        assert_eq!(None, scala.tick(11));
+        assert_eq!(None, scala.tick(14));
+        assert_eq!(Some(15.0), scala.tick(15));
+    }
+
+    #[test]
+    fn tick_scaled_test() {
+        // Each row spans 20 USD, so there is a tick every 100 USD
+        let scala = YScala::new(&Currency::bitcoin(), ScaleY::Linear { factor: 0.05, origin: 0.0 }, 1);
+
+        assert_eq!(Some(100.0), scala.tick(5));
+        assert_eq!(None, scala.tick(6));
+        assert_eq!(Some(200.0), scala.tick(10));
+    }
+
+    #[test]
+    fn tick_sub_cell_test() {
+        // Each row spans 4 dots of 1 USD, so there is a tick every 20 USD
+        let scala = YScala::new(&Currency::bitcoin(), ScaleY::Linear { factor: 1.0, origin: 0.0 }, 4);
+
+        assert_eq!(Some(20.0), scala.tick(20));
+        assert_eq!(Some(20.0), scala.tick(17));
+        assert_eq!(None, scala.tick(21));
+        assert_eq!(None, scala.tick(13));
+    }
+
+    #[test]
+    fn tick_logarithmic_test() {
+        // Each row spans a factor of 10^(1/12), so the ticks get further apart the higher the price
+        let scala = YScala::new(&Currency::bitcoin(), ScaleY::Logarithmic { factor: 12.0 / 10f64.ln(), origin: 1_000f64.ln(), offset: 0.0 }, 1);
+        let ticks: Vec<(CoordinatePrecision, f64)> = (0..24).filter_map(|row| scala.tick(row).map(|tick| (row, tick))).collect();
+
+        assert_eq!(
+            vec![(0, 1_000.0), (3, 2_000.0), (8, 5_000.0), (12, 10_000.0), (15, 20_000.0), (20, 50_000.0)],
+            ticks
+        );
+    }
+
+    #[test]
+    fn tick_steep_logarithmic_test() {
+        // Each row spans a factor of 10^(1/4), so only powers of ten are labeled
+        let scala = YScala::new(&Currency::bitcoin(), ScaleY::Logarithmic { factor: 4.0 / 10f64.ln(), origin: 0.0, offset: 0.0 }, 1);
+
+        let ticks: Vec<(CoordinatePrecision, f64)> = (0..8).filter_map(|row| scala.tick(row).map(|tick| (row, tick))).collect();
+        assert_eq!(vec![(0, 1.0), (4, 10.0)], ticks);
+    }
+
+    #[test]
+    fn draw_row_test() {
+        let scala = YScala::new(&Currency::bitcoin(), ScaleY::Linear { factor: 1.0, origin: 0.0 }, 1);
+
+        assert_eq!("  10.00 |", scala.draw_row(10, 9));
+        assert_eq!("        |", scala.draw_row(11, 9));
+        assert_eq!("    |", scala.draw_row(10, 5));
+    }
+
+    #[test]
+    fn get_width_test() {
+        let rate = |price: f64| ::rate::Rate::from_price(Currency::bitcoin(), "USD", ::rate::Price::from_f64(price));
+
+        assert_eq!(0, get_width::<::rate::Rate>(&[]));
+        assert_eq!(10, get_width(&[rate(9.5), rate(19234.56), rate(999.0)]));
+    }
+
+    #[test]
+    fn get_percent_width_test() {
+        let rate = |price: f64| ::rate::Rate::from_price(Currency::bitcoin(), "USD", Price::from_f64(price));
+
+        assert_eq!(0, get_percent_width::<::rate::Rate>(&[], Price::from_f64(100.0)));
+        assert_eq!(9, get_percent_width(&[rate(95.0), rate(112.5)], Price::from_f64(100.0)));
+        assert_eq!(10, get_percent_width(&[rate(95.0), rate(212.5)], Price::from_f64(100.0)));
+    }
+
+    #[test]
+    fn get_range_width_test() {
+        assert_eq!(0, get_range_width(&YRange::auto(), 2, false));
+        assert_eq!(10, get_range_width(&YRange::new(Some(9.5), Some(19234.56)).unwrap(), 2, false));
+        assert_eq!(8, get_range_width(&YRange::new(None, Some(2.5)).unwrap(), 2, true));
+    }
+
+    #[test]
+    fn percent_tick_test() {
+        // One row per 0.01% starting at -0.1%, so there is a tick every 0.05%
+        let scala = YScala::percent(ScaleY::Linear { factor: 1.0, origin: -10.0 }, 1);
+
+        assert_eq!(Some(0.0), scala.tick(10));
+        assert_eq!(None, scala.tick(11));
+        assert_eq!(Some(-0.05), scala.tick(5));
+        assert_eq!("  +0.00% |", scala.draw_row(10, 10));
+        assert_eq!("  -0.05% |", scala.draw_row(5, 10));
+        assert_eq!("  +0.10% |", scala.draw_row(20, 10));
+    }
+
+    #[test]
+    fn label_width_test() {
+        assert_eq!(10, label_width(19234.56, 2, false));
+        assert_eq!(7, label_width(0.5, 3, false));
+        assert_eq!(8, label_width(0.5, 2, true));
+    }
+}
@@ -1,5 +1,6 @@
 use serde::{Serialize, Deserialize};
 use std::collections::HashMap;
+use rate::Price;
 /*
 {
   "USD" : {"15m" : 478.68, "last" : 478.68, "buy" : 478.55, "sell" : 478.68,  "symbol" : "$"},
@@ -28,10 +29,10 @@ use std::collections::HashMap;
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct CurrencyRate {
-    pub last: f32,
+    pub last: Price,
     symbol: String,
-    buy: f32,
-    sell: f32,
+    buy: Price,
+    sell: Price,
 }
 
 
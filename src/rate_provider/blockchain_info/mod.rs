@@ -18,6 +18,14 @@ impl BlockchainInfo {
             Err(e) => Err(ProviderError::new(e.to_string())),
         }
     }
+
+    fn convert_to_prices(response: &str) -> Result<rate::PriceMap, ProviderError> {
+        let currency_rates: CurrencyRateMap = Self::convert_to_internal_rates(response)?;
+
+        Ok(currency_rates.iter()
+            .map(|(code, rate)| (code.to_string(), rate.last))
+            .collect())
+    }
 }
 
 impl RateProvider for BlockchainInfo {
@@ -25,28 +33,16 @@ impl RateProvider for BlockchainInfo {
         "BlockchainInfo"
     }
 
-    fn get(currency: Currency) -> Result<rate::Rate, ProviderError> {
-        if currency != Currency::Bitcoin {
+    fn get(currency: &Currency, quote: &str) -> Result<rate::Rate, ProviderError> {
+        if *currency != Currency::bitcoin() {
             return Err(ProviderError::new("This provider only support Bitcoin"));
         }
         let response = Self::download("https://blockchain.info/ticker")?;
-        Self::convert(&response)
+        super::build_rate(currency, quote, Self::convert_to_prices(&response)?)
     }
 
     fn convert(response: &str) -> Result<rate::Rate, ProviderError> {
-        let currency_rates: CurrencyRateMap = Self::convert_to_internal_rates(response)?;
-
-        Ok(rate::Rate::new(
-            Currency::Bitcoin,
-            match currency_rates.get("USD") {
-                Some(rate) => rate.last,
-                None => 0.0,
-            },
-            match currency_rates.get("EUR") {
-                Some(rate) => rate.last,
-                None => 0.0,
-            },
-        ))
+        super::build_rate(&Currency::bitcoin(), rate::DEFAULT_QUOTE, Self::convert_to_prices(response)?)
     }
 }
 
@@ -56,8 +52,22 @@ mod tests {
 
     #[test]
     fn get_test() {
-        let result: Result<rate::Rate, ProviderError> = <BlockchainInfo as RateProvider>::get(Currency::Bitcoin);
+        let result: Result<rate::Rate, ProviderError> = <BlockchainInfo as RateProvider>::get(&Currency::bitcoin(), "USD");
 
         assert!(result.is_ok())
     }
+
+    #[test]
+    fn convert_test() {
+        let rate = <BlockchainInfo as RateProvider>::convert(r#"{
+          "USD" : {"15m" : 478.68, "last" : 478.68, "buy" : 478.55, "sell" : 478.68,  "symbol" : "$"},
+          "CHF" : {"15m" : 447.19, "last" : 447.19, "buy" : 447.07, "sell" : 447.19,  "symbol" : "CHF"},
+          "GBP" : {"15m" : 297.4, "last" : 297.4, "buy" : 297.32, "sell" : 297.4,  "symbol" : "£"}
+        }"#).unwrap();
+
+        assert_eq!("USD", rate.quote());
+        assert_eq!("478.68", rate.price().to_string());
+        assert_eq!("447.19", rate.price_in("CHF").unwrap().to_string());
+        assert_eq!("297.4", rate.price_in("GBP").unwrap().to_string());
+    }
 }
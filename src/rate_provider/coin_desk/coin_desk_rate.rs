@@ -1,6 +1,7 @@
 use serde::{Serialize, Deserialize};
-
-//use std::collections::HashMap;
+use std::collections::HashMap;
+use chrono::prelude::*;
+use rate::Price;
 // https://api.coindesk.com/v1/bpi/currentprice/CNY.json
 
 //{
@@ -29,31 +30,33 @@ use serde::{Serialize, Deserialize};
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Bpi {
     pub code: String,
-    #[serde(rename = "rate_float")]
-    pub rate: f32,
+    // Formatted with thousands separators, e.g. "40,202.5000"
+    pub rate: String,
 }
 
-// Flexible type
-//pub type BpiMap = HashMap<String, Bpi>;
-
-// Strict type - easy for mapping
-#[derive(Serialize, Deserialize, Debug)]
-pub struct BpiMap {
-    #[serde(rename = "USD")]
-    pub usd: Bpi,
-    #[serde(rename = "EUR")]
-    pub eur: Bpi,
+impl Bpi {
+    pub fn price(&self) -> Option<Price> {
+        self.rate.replace(',', "").parse().ok()
+    }
 }
 
+pub type BpiMap = HashMap<String, Bpi>;
+
 #[derive(Serialize, Deserialize, Debug)]
-struct Time {
+pub struct Time {
     updated: String,
     #[serde(rename = "updatedISO")]
     updated_iso: String,
 }
 
+impl Time {
+    pub fn updated(&self) -> Option<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(&self.updated_iso).ok().map(|time| time.with_timezone(&Utc))
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Rate {
-    time: Time,
+    pub time: Time,
     pub bpi: BpiMap,
 }
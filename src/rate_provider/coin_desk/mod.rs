@@ -18,26 +18,40 @@ impl CoinDesk {
             Err(e) => Err(ProviderError::new(e.to_string())),
         }
     }
+
+    fn convert_with_quote(response: &str, quote: &str) -> Result<rate::Rate, ProviderError> {
+        let internal_rate = Self::convert_to_internal_rate(response)?;
+
+        let mut prices = rate::PriceMap::new();
+        for bpi in internal_rate.bpi.values() {
+            match bpi.price() {
+                Some(price) => prices.insert(bpi.code.to_uppercase(), price),
+                None => return Err(ProviderError::new(format!("Invalid rate '{}' for {}", bpi.rate, bpi.code))),
+            };
+        }
+
+        let rate = super::build_rate(&Currency::bitcoin(), quote, prices)?;
+
+        Ok(rate.with_source_time(internal_rate.time.updated()))
+    }
 }
 
 impl RateProvider for CoinDesk {
     fn get_name() -> &'static str {
         "CoinDesk"
     }
-    fn get(currency: Currency) -> Result<rate::Rate, super::ProviderError> {
-        if currency != Currency::Bitcoin {
+    fn get(currency: &Currency, quote: &str) -> Result<rate::Rate, super::ProviderError> {
+        if *currency != Currency::bitcoin() {
             return Err(ProviderError::new("This provider only support Bitcoin"));
         }
-        let response = Self::download("https://api.coindesk.com/v1/bpi/currentprice/EUR.json")?;
-        Self::convert(&response)
+        let response = Self::download(&format!(
+            "https://api.coindesk.com/v1/bpi/currentprice/{}.json",
+            quote.to_uppercase()
+        ))?;
+        Self::convert_with_quote(&response, quote)
     }
     fn convert(response: &str) -> Result<rate::Rate, super::ProviderError> {
-        let internal_rate = Self::convert_to_internal_rate(response)?;
-        Ok(rate::Rate::new(
-            Currency::Bitcoin,
-            internal_rate.bpi.usd.rate,
-            internal_rate.bpi.eur.rate,
-        ))
+        Self::convert_with_quote(response, rate::DEFAULT_QUOTE)
     }
 }
 
@@ -48,8 +62,46 @@ mod tests {
 
     #[test]
     fn get_test() {
-        let result: Result<rate::Rate, ProviderError> = <CoinDesk as RateProvider>::get(Currency::Bitcoin);
+        let result: Result<rate::Rate, ProviderError> = <CoinDesk as RateProvider>::get(&Currency::bitcoin(), "USD");
 
         assert!(result.is_ok())
     }
+
+    #[test]
+    fn convert_test() {
+        let rate = <CoinDesk as RateProvider>::convert(r#"{
+            "bpi": {
+                "CNY": {"code": "CNY", "description": "Chinese Yuan", "rate": "40,202.5000", "rate_float": 40202.5},
+                "USD": {"code": "USD", "description": "United States Dollar", "rate": "11,740.1250", "rate_float": 11740.125}
+            },
+            "disclaimer": "This data was produced from the CoinDesk Bitcoin Price Index (USD & CNY respectively).",
+            "time": {
+                "updated": "Dec 3, 2017 19:05:00 UTC",
+                "updatedISO": "2017-12-03T19:05:00+00:00",
+                "updateduk": "Dec 3, 2017 at 19:05 GMT"
+            }
+        }"#).unwrap();
+
+        assert_eq!("USD", rate.quote());
+        assert_eq!("11740.125", rate.price().to_string());
+        assert_eq!("40202.5", rate.price_in("CNY").unwrap().to_string());
+        assert_eq!("2017-12-03T19:05:00+00:00", rate.source_time.unwrap().to_rfc3339());
+    }
+
+    #[test]
+    fn convert_exact_decimal_test() {
+        // The rate is kept as given instead of its nearest float
+        let rate = <CoinDesk as RateProvider>::convert(r#"{
+            "bpi": {
+                "USD": {"code": "USD", "description": "United States Dollar", "rate": "11,740.1125", "rate_float": 11740.1125}
+            },
+            "time": {
+                "updated": "Dec 3, 2017 19:05:00 UTC",
+                "updatedISO": "2017-12-03T19:05:00+00:00",
+                "updateduk": "Dec 3, 2017 at 19:05 GMT"
+            }
+        }"#).unwrap();
+
+        assert_eq!("11740.1125", rate.price().to_string());
+    }
 }
@@ -1,4 +1,8 @@
 use serde::{Serialize, Deserialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use rate::PriceMap;
+use chrono::prelude::*;
 #[derive(Serialize, Deserialize, Debug)]
 pub struct IntermediateRate {
     //": "bitcoin",
@@ -14,7 +18,8 @@ pub struct IntermediateRate {
     //": "1.0",
     pub price_btc: String,
     //": "72855700.0",
-    //pub 24h_volume_usd: String,
+    #[serde(rename = "24h_volume_usd")]
+    pub volume_usd: Option<String>,
     //": "9080883500.0",
     pub market_cap_usd: String,
     //": "15844176.0",
@@ -30,12 +35,47 @@ pub struct IntermediateRate {
     //": "1472762067"
     pub last_updated: String,
 
-    // "7278.69583695",
-    pub price_eur: String,
-    // "3693903712.5",
-    //pub 24h_volume_eur: String,
-    // "121561499173"
-    pub market_cap_eur: String,
+    // Fields added by the `convert` parameter, e.g. for `convert=EUR`:
+    // "price_eur": "7278.69583695",
+    // "24h_volume_eur": "3693903712.5",
+    // "market_cap_eur": "121561499173"
+    #[serde(flatten)]
+    pub converted: HashMap<String, Value>,
+}
+
+impl IntermediateRate {
+    pub fn last_updated(&self) -> Option<DateTime<Utc>> {
+        match self.last_updated.parse::<i64>() {
+            Ok(timestamp) => Utc.timestamp_opt(timestamp, 0).single(),
+            Err(_) => None,
+        }
+    }
+
+    /// Return the trading volume of the last 24 hours in USD
+    pub fn volume(&self) -> Option<f64> {
+        self.volume_usd.as_ref().and_then(|volume| volume.parse().ok())
+    }
+
+    /// Collect the prices for USD, BTC and the converted quote currency
+    pub fn prices(&self) -> PriceMap {
+        let mut prices = PriceMap::new();
+        if let Ok(price) = self.price_usd.parse() {
+            prices.insert("USD".to_string(), price);
+        }
+        if let Ok(price) = self.price_btc.parse() {
+            prices.insert("BTC".to_string(), price);
+        }
+        for (key, value) in &self.converted {
+            if !key.starts_with("price_") {
+                continue;
+            }
+            if let Some(Ok(price)) = value.as_str().map(|v| v.parse()) {
+                prices.insert(key["price_".len()..].to_uppercase(), price);
+            }
+        }
+
+        prices
+    }
 }
 
 pub type IntermediateRateCollection = Vec<IntermediateRate>;
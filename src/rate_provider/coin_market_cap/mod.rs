@@ -6,7 +6,6 @@ use super::ProviderError;
 use super::RateProvider;
 use super::Currency;
 use self::intermediate_rate::*;
-use serde::{Serialize, Deserialize};
 
 pub struct CoinMarketCap {}
 
@@ -25,19 +24,27 @@ impl RateProvider for CoinMarketCap {
     fn get_name() -> &'static str {
         "CoinMarketCap"
     }
-    fn get_all() -> Result<Vec<rate::Rate>, ProviderError> {
-        let response = Self::download("https://api.coinmarketcap.com/v1/ticker/?convert=EUR&limit=10")?;
-        Self::convert_all(&response)
+    fn get_all(quote: &str) -> Result<Vec<rate::Rate>, ProviderError> {
+        let response = Self::download(&format!(
+            "https://api.coinmarketcap.com/v1/ticker/?convert={}&limit=100",
+            quote.to_uppercase()
+        ))?;
+        Ok(Self::convert_all(&response)?.into_iter()
+            .map(|rate| rate.with_quote(quote))
+            .collect())
     }
 
-    fn get(currency: Currency) -> Result<rate::Rate, ProviderError> {
-        let all = CoinMarketCap::get_all()?;
+    fn get(currency: &Currency, quote: &str) -> Result<rate::Rate, ProviderError> {
+        let all = CoinMarketCap::get_all(quote)?;
 
         match all.into_iter()
             .find(|rate| {
-                currency == rate.currency
+                *currency == rate.currency
             }) {
-            Some(rate) => Ok(rate),
+            // Keep the requested currency, since the listing only guesses the display precision
+            Some(rate) => Ok(super::build_rate(currency, quote, rate.prices)?
+                .with_source_time(rate.source_time)
+                .with_volume(rate.volume)),
             None => Err(ProviderError::new(format!("No rate for currency {} found", currency.name()))),
         }
     }
@@ -49,14 +56,18 @@ impl RateProvider for CoinMarketCap {
         let mut rates = Vec::with_capacity(internal_rates.len());
 
         for internal_rate in internal_rates {
-            let currency = Currency::new(internal_rate.name);
-            if let Some(currency) = currency {
-                rates.push(rate::Rate::new(
-                    currency,
-                    internal_rate.price_usd.parse().unwrap_or(0.0),
-                    internal_rate.price_eur.parse().unwrap_or(0.0),
-                ));
-            }
+            let prices = internal_rate.prices();
+            let source_time = internal_rate.last_updated();
+            let volume = internal_rate.volume();
+            let currency = Currency::new(
+                internal_rate.symbol,
+                internal_rate.name,
+                vec![internal_rate.id],
+                Currency::precision_for_price(prices.get("USD").cloned().unwrap_or_default()),
+            );
+            rates.push(rate::Rate::new(currency, rate::DEFAULT_QUOTE, prices)
+                .with_source_time(source_time)
+                .with_volume(volume));
         }
 
         Ok(rates)
@@ -70,8 +81,42 @@ mod tests {
 
     #[test]
     fn get_test() {
-        let result: Result<rate::Rate, ProviderError> = <CoinMarketCap as RateProvider>::get(Currency::Bitcoin);
+        let result: Result<rate::Rate, ProviderError> = <CoinMarketCap as RateProvider>::get(&Currency::bitcoin(), "USD");
 
         assert!(result.is_ok())
     }
+
+    #[test]
+    fn convert_all_test() {
+        let rates = <CoinMarketCap as RateProvider>::convert_all(r#"[{
+            "id": "dogecoin",
+            "name": "Dogecoin",
+            "symbol": "DOGE",
+            "rank": "30",
+            "price_usd": "0.00573",
+            "price_btc": "0.0000005",
+            "24h_volume_usd": "72855700.0",
+            "market_cap_usd": "9080883500.0",
+            "available_supply": "15844176.0",
+            "total_supply": "15844176.0",
+            "percent_change_1h": "0.04",
+            "percent_change_24h": "-0.3",
+            "percent_change_7d": "-0.57",
+            "last_updated": "1472762067",
+            "price_gbp": "0.00451",
+            "24h_volume_gbp": "3693903712.5",
+            "market_cap_gbp": "121561499173"
+        }]"#).unwrap();
+
+        assert_eq!(1, rates.len());
+        let rate = &rates[0];
+        assert!(rate.currency.matches("doge"));
+        assert!(rate.currency.matches("dogecoin"));
+        assert_eq!(5, rate.currency.precision());
+        assert_eq!("0.00573", rate.price().to_string());
+        assert_eq!("0.00451", rate.price_in("GBP").unwrap().to_string());
+        assert_eq!("0.0000005", rate.price_in("BTC").unwrap().to_string());
+        assert_eq!(1472762067, rate.source_time.unwrap().timestamp());
+        assert_eq!(Some(72855700.0), rate.volume);
+    }
 }
@@ -18,17 +18,34 @@ impl CryptoCompare {
             Err(e) => Err(ProviderError::new(e.to_string())),
         }
     }
+
+    /// Build the `tsyms` list with the requested quote and the common fiat currencies
+    fn build_quote_list(quote: &str) -> String {
+        let quote = quote.to_uppercase();
+        let mut quotes = vec![quote.as_str()];
+        for common in &["USD", "EUR"] {
+            if quote != *common {
+                quotes.push(common);
+            }
+        }
+
+        quotes.join(",")
+    }
 }
 
 impl RateProvider for CryptoCompare {
     fn get_name() -> &'static str {
         "CryptoCompare"
     }
-    fn get(currency: Currency) -> Result<rate::Rate, ProviderError> {
-        let response = Self::download(&format!("https://min-api.cryptocompare.com/data/price?fsym={}&tsyms=USD,EUR", currency.symbol()))?;
+    fn get(currency: &Currency, quote: &str) -> Result<rate::Rate, ProviderError> {
+        let response = Self::download(&format!(
+            "https://min-api.cryptocompare.com/data/price?fsym={}&tsyms={}",
+            currency.symbol(),
+            Self::build_quote_list(quote)
+        ))?;
         let internal_rate = Self::convert_to_internal_rate(&response)?;
 
-        Ok(rate::Rate::new(currency, internal_rate.usd, internal_rate.eur))
+        super::build_rate(currency, quote, internal_rate.into_iter().collect())
     }
 }
 
@@ -39,8 +56,15 @@ mod tests {
 
     #[test]
     fn get_test() {
-        let result: Result<rate::Rate, ProviderError> = <CryptoCompare as RateProvider>::get(Currency::Bitcoin);
+        let result: Result<rate::Rate, ProviderError> = <CryptoCompare as RateProvider>::get(&Currency::bitcoin(), "USD");
 
         assert!(result.is_ok())
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn build_quote_list_test() {
+        assert_eq!("USD,EUR", CryptoCompare::build_quote_list("usd"));
+        assert_eq!("EUR,USD", CryptoCompare::build_quote_list("EUR"));
+        assert_eq!("CHF,USD,EUR", CryptoCompare::build_quote_list("chf"));
+    }
+}
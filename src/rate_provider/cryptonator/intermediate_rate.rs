@@ -1,4 +1,6 @@
 use serde::{Serialize, Deserialize};
+use rate::Price;
+use chrono::prelude::*;
 //{
 //    "ticker": {
 //        "base": "LTC",
@@ -16,17 +18,26 @@ pub struct Ticker {
     pub base: String,
     pub target: String,
     pub price: String,
-    volume: String,
+    pub volume: String,
     change: String,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct IntermediateRate {
     pub ticker: Ticker,
+    pub timestamp: i64,
 }
 
 impl IntermediateRate {
-    pub fn price(&self)->f32 {
-        self.ticker.price.parse().unwrap_or(0.0)
+    pub fn price(&self) -> Price {
+        self.ticker.price.parse().unwrap_or_default()
+    }
+
+    pub fn volume(&self) -> Option<f64> {
+        self.ticker.volume.parse().ok()
+    }
+
+    pub fn time(&self) -> Option<DateTime<Utc>> {
+        Utc.timestamp_opt(self.timestamp, 0).single()
     }
 }
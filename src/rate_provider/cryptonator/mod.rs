@@ -51,11 +51,12 @@ impl RateProvider for Cryptonator {
     fn get_name() -> &'static str {
         "Cryptonator"
     }
-    fn get(currency: Currency) -> Result<rate::Rate, ProviderError> {
-        let usd_rate:IntermediateRate = Self::get_pair_in_internal_rate(&currency, "usd")?;
-        let eur_rate:IntermediateRate = Self::get_pair_in_internal_rate(&currency, "eur")?;
+    fn get(currency: &Currency, quote: &str) -> Result<rate::Rate, ProviderError> {
+        let quote_rate:IntermediateRate = Self::get_pair_in_internal_rate(currency, quote)?;
 
-        Ok(rate::Rate::new(currency, usd_rate.price(), eur_rate.price()))
+        Ok(rate::Rate::from_price(currency.clone(), quote, quote_rate.price())
+            .with_source_time(quote_rate.time())
+            .with_volume(quote_rate.volume()))
     }
 }
 
@@ -66,7 +67,7 @@ mod tests {
 
     #[test]
     fn get_test() {
-        let result: Result<rate::Rate, ProviderError> = <Cryptonator as RateProvider>::get(Currency::Bitcoin);
+        let result: Result<rate::Rate, ProviderError> = <Cryptonator as RateProvider>::get(&Currency::bitcoin(), "USD");
 
         assert!(result.is_ok())
     }
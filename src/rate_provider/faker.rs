@@ -14,30 +14,45 @@ impl RateProvider for Faker {
         "Faker"
     }
 
-    fn get(currency: Currency) -> Result<rate::Rate, ProviderError> {
+    fn get(currency: &Currency, quote: &str) -> Result<rate::Rate, ProviderError> {
         let mut rng = rand::thread_rng();
-        Ok(match currency {
-            Currency::Bitcoin => rate::Rate::new(
-                currency,
-                rand::thread_rng().gen_range(15_000.0f32..=21_000.0f32),
-                rand::thread_rng().gen_range(13_000.0f32..=15_000.0f32),
+        let (price_usd, price_eur) = match currency.symbol() {
+            "BTC" => (
+                rng.gen_range(15_000.0f64..=21_000.0f64),
+                rng.gen_range(13_000.0f64..=15_000.0f64),
             ),
-            Currency::Ethereum => rate::Rate::new(
-                currency,
-                rand::thread_rng().gen_range(1_050.0f32..=1_200.0f32),
-                rand::thread_rng().gen_range(810.0f32..=960.0f32),
+            "ETH" => (
+                rng.gen_range(1_050.0f64..=1_200.0f64),
+                rng.gen_range(810.0f64..=960.0f64),
             ),
-            Currency::Litecoin => rate::Rate::new(
-                currency,
-                rand::thread_rng().gen_range(150.0f32..=360.0f32),
-                rand::thread_rng().gen_range(128.0f32..=306.0f32),
+            "LTC" => (
+                rng.gen_range(150.0f64..=360.0f64),
+                rng.gen_range(128.0f64..=306.0f64),
             ),
-            Currency::Ripple => rate::Rate::new(
-                currency,
-                rand::thread_rng().gen_range(2.21f32..=3.40f32),
-                rand::thread_rng().gen_range(1.84f32..=2.82f32),
-            )
-        })
+            "XRP" => (
+                rng.gen_range(2.21f64..=3.40f64),
+                rng.gen_range(1.84f64..=2.82f64),
+            ),
+            _ => (
+                rng.gen_range(0.90f64..=1.10f64),
+                rng.gen_range(0.80f64..=0.95f64),
+            ),
+        };
+
+        // Round to the currency's precision, like a real provider would
+        let precision = currency.precision() as u32;
+        let price_usd = rate::Price::from_f64(price_usd).round_to(precision);
+        let price_eur = rate::Price::from_f64(price_eur).round_to(precision);
+
+        let mut prices = rate::PriceMap::new();
+        // Other quote currencies are faked with the USD price
+        prices.insert(quote.to_uppercase(), price_usd);
+        prices.insert("USD".to_string(), price_usd);
+        prices.insert("EUR".to_string(), price_eur);
+
+        let volume = rng.gen_range(1_000_000.0f64..=50_000_000.0f64).round();
+
+        Ok(super::build_rate(currency, quote, prices)?.with_volume(Some(volume)))
     }
 }
 
@@ -47,7 +62,22 @@ mod tests {
 
     #[test]
     fn get_test() {
-        let result: Result<rate::Rate, ProviderError> = <Faker as RateProvider>::get(Currency::Bitcoin);
+        let result: Result<rate::Rate, ProviderError> = <Faker as RateProvider>::get(&Currency::bitcoin(), "USD");
         assert!(result.is_ok())
     }
+
+    #[test]
+    fn get_unknown_currency_test() {
+        let result: Result<rate::Rate, ProviderError> = <Faker as RateProvider>::get(&Currency::from_symbol("doge"), "USD");
+        assert_eq!("DOGE", result.unwrap().currency.symbol())
+    }
+
+    #[test]
+    fn get_quote_test() {
+        let rate = <Faker as RateProvider>::get(&Currency::bitcoin(), "eur").unwrap();
+        assert_eq!("EUR", rate.quote());
+        assert_eq!(rate.price_in("EUR").unwrap(), rate.price());
+        assert!(rate.price_in("USD").is_some());
+        assert!(rate.volume.is_some());
+    }
 }
@@ -23,8 +23,8 @@ pub use self::faker::Faker;
 
 pub trait RateProvider {
     fn get_name() -> &'static str;
-    fn get(currency: Currency) -> Result<rate::Rate, ProviderError>;
-    fn get_all() -> Result<Vec<rate::Rate>, ProviderError> {
+    fn get(currency: &Currency, quote: &str) -> Result<rate::Rate, ProviderError>;
+    fn get_all(_quote: &str) -> Result<Vec<rate::Rate>, ProviderError> {
         Ok(vec![])
     }
 
@@ -62,6 +62,7 @@ pub trait RateProvider {
         Ok((output, load_time))
     }
 
+    #[allow(unused)]
     fn convert(response: &str) -> Result<rate::Rate, ProviderError> {
         let deserialized_result: serde_json::Result<rate::Rate> = serde_json::from_str(&response);
 
@@ -85,16 +86,45 @@ pub trait RateProvider {
     }
 }
 
+/// Build a rate driven by `quote`, failing if the provider did not return a price for it
+fn build_rate(currency: &Currency, quote: &str, prices: rate::PriceMap) -> Result<rate::Rate, ProviderError> {
+    let quote = quote.to_uppercase();
+    if !prices.contains_key(&quote) {
+        return Err(ProviderError::new(format!("No {} price for currency {} found", quote, currency.name())));
+    }
+
+    Ok(rate::Rate::new(currency.clone(), quote, prices))
+}
+
+#[allow(unused)]
+pub fn get<S>(provider_type: S, currency: &Currency, quote: &str) -> Result<rate::Rate, ProviderError>
+    where S: Into<String> {
+    let provider_type_string = provider_type.into();
+    match provider_type_string.to_lowercase().as_str() {
+        "coindesk" | "coin_desk" => coin_desk::CoinDesk::get(currency, quote),
+        "coinmarketcap" | "coin_market_cap" => coin_market_cap::CoinMarketCap::get(currency, quote),
+        "blockchaininfo" | "blockchain_info" => blockchain_info::BlockchainInfo::get(currency, quote),
+        "cryptocompare" | "crypto_compare" => crypto_compare::CryptoCompare::get(currency, quote),
+        "cryptonator" => cryptonator::Cryptonator::get(currency, quote),
+        "faker" => faker::Faker::get(currency, quote),
+        _ => Err(ProviderError::new(format!("No provider for type '{}' found", provider_type_string)))
+    }
+}
+
+/// Fetch the listing of all rates the provider knows about
+///
+/// Providers without a listing return an empty collection
 #[allow(unused)]
-pub fn get<S>(provider_type: S, currency: Currency) -> Result<rate::Rate, ProviderError>
+pub fn get_all<S>(provider_type: S, quote: &str) -> Result<Vec<rate::Rate>, ProviderError>
     where S: Into<String> {
     let provider_type_string = provider_type.into();
     match provider_type_string.to_lowercase().as_str() {
-        "coindesk" | "coin_desk" => coin_desk::CoinDesk::get(currency),
-        "coinmarketcap" | "coin_market_cap" => coin_market_cap::CoinMarketCap::get(currency),
-        "blockchaininfo" | "blockchain_info" => blockchain_info::BlockchainInfo::get(currency),
-        "cryptocompare" | "crypto_compare" => crypto_compare::CryptoCompare::get(currency),
-        "faker" => faker::Faker::get(currency),
+        "coindesk" | "coin_desk" => coin_desk::CoinDesk::get_all(quote),
+        "coinmarketcap" | "coin_market_cap" => coin_market_cap::CoinMarketCap::get_all(quote),
+        "blockchaininfo" | "blockchain_info" => blockchain_info::BlockchainInfo::get_all(quote),
+        "cryptocompare" | "crypto_compare" => crypto_compare::CryptoCompare::get_all(quote),
+        "cryptonator" => cryptonator::Cryptonator::get_all(quote),
+        "faker" => faker::Faker::get_all(quote),
         _ => Err(ProviderError::new(format!("No provider for type '{}' found", provider_type_string)))
     }
 }
@@ -109,6 +139,7 @@ pub fn get_name<S>(provider_type: S) -> Option<&'static str>
         "coinmarketcap" | "coin_market_cap" => Some(coin_market_cap::CoinMarketCap::get_name()),
         "blockchaininfo" | "blockchain_info" => Some(blockchain_info::BlockchainInfo::get_name()),
         "cryptocompare" | "crypto_compare" => Some(crypto_compare::CryptoCompare::get_name()),
+        "cryptonator" => Some(cryptonator::Cryptonator::get_name()),
         "faker" => Some(faker::Faker::get_name()),
         _ => None,
     }
@@ -122,6 +153,7 @@ pub fn get_all_names() -> Vec<&'static str> {
         coin_market_cap::CoinMarketCap::get_name(),
         blockchain_info::BlockchainInfo::get_name(),
         crypto_compare::CryptoCompare::get_name(),
+        cryptonator::Cryptonator::get_name(),
         faker::Faker::get_name(),
     ]
 }
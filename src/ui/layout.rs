@@ -0,0 +1,97 @@
+use point::Point;
+use super::size::Size;
+use super::pixel::CoordinatePrecision;
+use super::error::Error;
+
+/// Region of the screen
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Pane {
+    pub origin: Point,
+    pub size: Size,
+}
+
+impl Pane {
+    pub fn new(origin: Point, size: Size) -> Self {
+        Pane { origin, size }
+    }
+
+    /// Return the pane shrunk to the given width and height (if they are smaller than the pane's)
+    pub fn shrink(&self, width: Option<CoordinatePrecision>, height: Option<CoordinatePrecision>) -> Self {
+        let width = width.map_or(self.size.width, |width| width.clamp(1, self.size.width));
+        let height = height.map_or(self.size.height, |height| height.clamp(1, self.size.height));
+
+        Pane::new(self.origin, Size::new(width, height))
+    }
+}
+
+/// Split the screen into a grid of `count` panes with `columns` panes per row
+///
+/// The panes are ordered row by row. If the screen can not be divided evenly, the panes in the last
+/// column and row take up the remaining space
+pub fn grid(size: Size, count: usize, columns: usize) -> Result<Vec<Pane>, Error> {
+    if count == 0 || columns == 0 {
+        return Ok(vec![]);
+    }
+    let columns = columns.min(count);
+    let rows = count.div_ceil(columns);
+    let pane_width = size.width as usize / columns;
+    let pane_height = size.height as usize / rows;
+    if pane_width == 0 || pane_height == 0 {
+        return Err(ui_error!(SizeError, "The screen is too small for {} rows of {} panes", rows, columns));
+    }
+
+    let span = |index: usize, length: usize, total: usize, parts: usize| {
+        if index + 1 == parts { total - index * length } else { length }
+    };
+
+    Ok((0..count)
+        .map(|index| {
+            let (column, row) = (index % columns, index / columns);
+            Pane::new(
+                Point::new((column * pane_width) as CoordinatePrecision, (row * pane_height) as CoordinatePrecision),
+                Size::new(
+                    span(column, pane_width, size.width as usize, columns) as CoordinatePrecision,
+                    span(row, pane_height, size.height as usize, rows) as CoordinatePrecision,
+                ),
+            )
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grid_test() {
+        let panes = grid(Size::new(81, 25), 4, 2).unwrap();
+
+        assert_eq!(4, panes.len());
+        assert_eq!(Pane::new(Point::new(0, 0), Size::new(40, 12)), panes[0]);
+        assert_eq!(Pane::new(Point::new(40, 0), Size::new(41, 12)), panes[1]);
+        assert_eq!(Pane::new(Point::new(0, 12), Size::new(40, 13)), panes[2]);
+        assert_eq!(Pane::new(Point::new(40, 12), Size::new(41, 13)), panes[3]);
+    }
+
+    #[test]
+    fn grid_incomplete_row_test() {
+        let panes = grid(Size::new(90, 20), 3, 2).unwrap();
+
+        assert_eq!(Pane::new(Point::new(0, 10), Size::new(45, 10)), panes[2]);
+        assert_eq!(1, grid(Size::new(90, 20), 1, 4).unwrap().len());
+        assert!(grid(Size::new(90, 20), 0, 2).unwrap().is_empty());
+    }
+
+    #[test]
+    fn grid_too_small_test() {
+        assert!(grid(Size::new(3, 20), 4, 4).is_err());
+    }
+
+    #[test]
+    fn shrink_test() {
+        let pane = Pane::new(Point::new(10, 5), Size::new(40, 12));
+
+        assert_eq!(Pane::new(Point::new(10, 5), Size::new(30, 12)), pane.shrink(Some(30), None));
+        assert_eq!(Pane::new(Point::new(10, 5), Size::new(40, 12)), pane.shrink(Some(300), Some(20)));
+    }
+}
@@ -10,6 +10,7 @@ mod screen_buffer;
 pub mod keyboard;
 pub mod screen;
 pub mod medium;
+pub mod layout;
 
 pub use self::size::Size;
 pub use self::element::Element;
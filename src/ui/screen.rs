@@ -83,7 +83,8 @@ impl<T: MediumTrait + Debug> Screen<T> {
 
     /// Insert the text at the given point with support for multi-line text
     ///
-    /// An error will be returned if one the text's characters does not fit into the underlying buffer
+    /// Each line starts at the point's `x` coordinate. An error will be returned if one the text's
+    /// characters does not fit into the underlying buffer
     ///
     /// Keep in mind, that these operations are not transactional. If an error occurs with the nth
     /// character, the previous characters are still stored
@@ -91,6 +92,21 @@ impl<T: MediumTrait + Debug> Screen<T> {
         self.draw_multi_line_text_wrapping(point, text, false)
     }
 
+    /// Insert the multi-line text at the given point, leaving out the characters which do not fit
+    /// into a region of the given size
+    ///
+    /// Each line starts at the point's `x` coordinate
+    pub fn draw_multi_line_text_clipped<P: PointTrait + Debug>(&mut self, point: &P, text: &str, size: Size) -> Result<(), Error> {
+        for pixel in PixelSequence::from_str(text) {
+            if pixel.character() == '\n' || pixel.x() >= size.width || pixel.y() >= size.height {
+                continue;
+            }
+            self.buffer.draw_pixel(pixel.with_x_y(point.x() + pixel.x(), point.y() + pixel.y()))?;
+        }
+
+        Ok(())
+    }
+
     /// Insert the text at the given point with support for multi-line text
    ///
    /// An error will be returned if one the text's characters does not fit into the underlying buffer
@@ -124,8 +140,9 @@ impl<T: MediumTrait + Debug> Screen<T> {
             }
 
             if pixel.character() == '\n' {
+                // The following line starts below the given point
                 current_y += 1;
-                current_x = 0;
+                current_x = point.x();
                 index += 1;
 
                 continue;
@@ -253,6 +270,21 @@ mod test {
         assert_eq!("          \n          \n       hel\nlo        \n          ", screen.get_contents());
     }
 
+    #[test]
+    fn draw_multi_line_text_test() {
+        let mut screen = Screen::with_size(Size::new(10, 5)).unwrap();
+        assert!(screen.draw_multi_line_text(&Point::new(0, 0), "ab\ncd").is_ok());
+        assert!(screen.draw_multi_line_text(&Point::new(5, 2), "hello\nworld").is_ok());
+        assert_eq!("ab        \ncd        \n     hello\n     world\n          ", screen.get_contents());
+    }
+
+    #[test]
+    fn draw_multi_line_text_clipped_test() {
+        let mut screen = Screen::with_size(Size::new(10, 4)).unwrap();
+        assert!(screen.draw_multi_line_text_clipped(&Point::new(2, 1), "hello\nworld\nagain", Size::new(3, 2)).is_ok());
+        assert_eq!("          \n  hel     \n  wor     \n          ", screen.get_contents());
+    }
+
     #[test]
     fn draw_text_wrapping_overflow_test() {
         let mut screen = Screen::with_size(Size::new(10, 3)).unwrap();
@@ -44,6 +44,41 @@ pub fn str_left_pad<'a>(input: &'a str, length: usize, fill: char) -> Cow<'a, st
     return Cow::Owned(buf);
 }
 
+/// Format the number of seconds as a short human readable duration (e.g. "42s", "3m 5s", "2h 10m")
+pub fn format_duration(seconds: i64) -> String {
+    let seconds = seconds.max(0);
+    let (days, hours, minutes) = (seconds / 86_400, seconds / 3_600 % 24, seconds / 60 % 60);
+
+    if days > 0 {
+        format!("{}d {}h", days, hours)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else if minutes > 0 {
+        format!("{}m {}s", minutes, seconds % 60)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+/// Parse a duration like "30s", "5m", "1h" or "2d" into seconds
+///
+/// A number without unit is interpreted as seconds
+pub fn parse_duration(input: &str) -> Option<i64> {
+    let input = input.trim();
+    let (number, factor) = match input.chars().last() {
+        Some('s') => (&input[..input.len() - 1], 1),
+        Some('m') => (&input[..input.len() - 1], 60),
+        Some('h') => (&input[..input.len() - 1], 3_600),
+        Some('d') => (&input[..input.len() - 1], 86_400),
+        _ => (input, 1),
+    };
+
+    match number.parse::<i64>() {
+        Ok(value) if value > 0 => value.checked_mul(factor),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -83,4 +118,28 @@ mod tests {
         assert_eq!("💋 ", str_left_pad("💋 ", 2, '.'));
         assert_eq!("💋 ------", str_left_pad("💋 ------", 2, '.'));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn format_duration_test() {
+        assert_eq!("0s", format_duration(0));
+        assert_eq!("0s", format_duration(-4));
+        assert_eq!("42s", format_duration(42));
+        assert_eq!("3m 5s", format_duration(185));
+        assert_eq!("2h 10m", format_duration(7_845));
+        assert_eq!("3d 4h", format_duration(3 * 86_400 + 4 * 3_600 + 59));
+    }
+
+    #[test]
+    fn parse_duration_test() {
+        assert_eq!(Some(30), parse_duration("30"));
+        assert_eq!(Some(30), parse_duration("30s"));
+        assert_eq!(Some(300), parse_duration("5m"));
+        assert_eq!(Some(3_600), parse_duration("1h"));
+        assert_eq!(Some(172_800), parse_duration("2d"));
+        assert_eq!(None, parse_duration("0m"));
+        assert_eq!(None, parse_duration("-5"));
+        assert_eq!(None, parse_duration("m"));
+        assert_eq!(None, parse_duration("5w"));
+        assert_eq!(None, parse_duration(""));
+    }
+}